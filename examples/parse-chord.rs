@@ -15,6 +15,8 @@ pub fn main() {
             let midi_codes = generate_voicing(&chord, Some(d5_midi_code));
             // Save the midi file, for example to open it with GarageBand or any music software
             to_midi_file(&midi_codes, Path::new("my_chord"), 120, 4);
+            // Or hold it for a given time, without the tempo math
+            to_midi_file_seconds(&midi_codes, Path::new("my_chord_short"), 1.5);
         }
         Err(e) => {
             for e in e.errors {
@@ -37,11 +39,29 @@ use midly::{
 /// * `bpm` - Beats per minute.
 /// * `beats` - Duration in beats.
 pub fn to_midi_file(chord_notes: &[u8], name: &Path, bpm: u32, beats: u16) {
+    write_midi_file(chord_notes, name, bpm, TICKS_PER_BEAT as u32 * beats as u32);
+}
+
+/// Generate a MIDI file from Chord, holding it for a given time.
+/// The file uses a tempo of 120 bpm, and the duration is rounded to the millisecond.
+/// # Arguments
+/// * `chord_notes` - The notes of the chord in MIDI codes.
+/// * `name` - The path of the file to save without extension.
+/// * `seconds` - Duration in seconds.
+pub fn to_midi_file_seconds(chord_notes: &[u8], name: &Path, seconds: f32) {
+    let bpm = 120;
+    let ticks_per_second = TICKS_PER_BEAT as f32 * bpm as f32 / 60.0;
+    let ticks = (seconds.max(0.0) * ticks_per_second).round() as u32;
+    write_midi_file(chord_notes, name, bpm, ticks);
+}
+
+/// Ticks of the metrical timing of the generated files.
+const TICKS_PER_BEAT: u16 = 500;
+
+fn write_midi_file(chord_notes: &[u8], name: &Path, bpm: u32, note_duration: u32) {
     let mc_x_beat = 60 * 1_000_000 / bpm;
-    let ticks_per_beat: u16 = 500;
-    let ticks_per_quarter = ticks_per_beat * beats;
+    let ticks_per_beat = TICKS_PER_BEAT;
     let velocity = u7::new(64);
-    let note_duration = ticks_per_quarter;
     let mut events = vec![];
     let tempo = midly::MetaMessage::Tempo(mc_x_beat.into());
     events.push(TrackEvent {
@@ -67,7 +87,7 @@ pub fn to_midi_file(chord_notes: &[u8], name: &Path, bpm: u32, beats: u16) {
     for (i, &note) in chord_notes.iter().enumerate() {
         events.push(TrackEvent {
             delta: if i == 0 {
-                (note_duration as u32).into()
+                note_duration.into()
             } else {
                 0.into()
             },
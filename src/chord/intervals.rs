@@ -1,11 +1,13 @@
 //! Useful abstractions to work with intervals
 
+use serde::de::{self, Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
-use serde::Deserialize;
 use std::fmt::Display;
 
-/// Enum representing all possible intervals of a chord
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+/// Enum representing all possible intervals of a chord.
+/// Intervals are serialized in their chord notation form (see [Interval::to_chord_notation]), so a
+/// flat ninth is `"b9"` and a major seventh is `"Maj7"`, and they are deserialized from that same form.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 #[repr(u8)]
 pub enum Interval {
     Unison,
@@ -33,6 +35,33 @@ pub enum Interval {
     Thirteenth,
 }
 
+/// Every interval, in declaration order, so the position of an interval is its `u8` representation.
+pub(crate) static INTERVALS: [Interval; 23] = [
+    Interval::Unison,
+    Interval::MinorSecond,
+    Interval::MajorSecond,
+    Interval::MinorThird,
+    Interval::MajorThird,
+    Interval::PerfectFourth,
+    Interval::AugmentedFourth,
+    Interval::DiminishedFifth,
+    Interval::PerfectFifth,
+    Interval::AugmentedFifth,
+    Interval::MinorSixth,
+    Interval::MajorSixth,
+    Interval::DiminishedSeventh,
+    Interval::MinorSeventh,
+    Interval::MajorSeventh,
+    Interval::Octave,
+    Interval::FlatNinth,
+    Interval::Ninth,
+    Interval::SharpNinth,
+    Interval::Eleventh,
+    Interval::SharpEleventh,
+    Interval::FlatThirteenth,
+    Interval::Thirteenth,
+];
+
 impl Interval {
     /// Returns the semitone representation of the interval
     /// # Arguments
@@ -92,6 +121,87 @@ impl Interval {
         }
     }
 
+    /// Returns the just intonation frequency ratio of the interval, as a numerator and a denominator.
+    /// Ratios come from 5-limit just intonation, using the smallest ratios commonly given for each interval:
+    ///
+    /// | Interval | Ratio | Interval | Ratio |
+    /// |----------|-------|----------|-------|
+    /// | 1 | 1/1 | 6 | 5/3 |
+    /// | b2 | 16/15 | bb7 | 128/75 |
+    /// | 2 | 9/8 | 7 | 16/9 |
+    /// | b3 | 6/5 | maj7 | 15/8 |
+    /// | 3 | 5/4 | 8 | 2/1 |
+    /// | 4 | 4/3 | b9 | 32/15 |
+    /// | #4 | 45/32 | 9 | 9/4 |
+    /// | b5 | 64/45 | #9 | 12/5 |
+    /// | 5 | 3/2 | 11 | 8/3 |
+    /// | #5 | 25/16 | #11 | 45/16 |
+    /// | b6 | 8/5 | b13 | 16/5 |
+    /// |  |  | 13 | 10/3 |
+    ///
+    /// Extensions are their simple interval an octave up (see [Interval::simple]), so the sharp ninth is a minor
+    /// third an octave up.
+    /// # Arguments
+    /// * `self` - The interval
+    /// # Returns
+    /// * `(u32, u32)` - The ratio of the interval, in lowest terms
+    pub fn just_ratio(&self) -> (u32, u32) {
+        match self {
+            Interval::Unison => (1, 1),
+            Interval::MinorSecond => (16, 15),
+            Interval::MajorSecond => (9, 8),
+            Interval::MinorThird => (6, 5),
+            Interval::MajorThird => (5, 4),
+            Interval::PerfectFourth => (4, 3),
+            Interval::AugmentedFourth => (45, 32),
+            Interval::DiminishedFifth => (64, 45),
+            Interval::PerfectFifth => (3, 2),
+            Interval::AugmentedFifth => (25, 16),
+            Interval::MinorSixth => (8, 5),
+            Interval::MajorSixth => (5, 3),
+            Interval::DiminishedSeventh => (128, 75),
+            Interval::MinorSeventh => (16, 9),
+            Interval::MajorSeventh => (15, 8),
+            Interval::Octave => (2, 1),
+            Interval::FlatNinth => (32, 15),
+            Interval::Ninth => (9, 4),
+            Interval::SharpNinth => (12, 5),
+            Interval::Eleventh => (8, 3),
+            Interval::SharpEleventh => (45, 16),
+            Interval::FlatThirteenth => (16, 5),
+            Interval::Thirteenth => (10, 3),
+        }
+    }
+
+    /// Checks if the interval is wider than an octave, as extensions (9, 11, 13 and their alterations) are.
+    /// The octave itself is taken as simple.
+    /// # Arguments
+    /// * `self` - The interval
+    /// # Returns
+    /// * `bool` - True if the interval is compound
+    pub fn is_compound(&self) -> bool {
+        self.st() > 12
+    }
+
+    /// Folds a compound interval down an octave, returning simple intervals as they are.
+    /// Since there is no augmented second, `SharpNinth` folds into `MinorThird`.
+    /// # Arguments
+    /// * `self` - The interval
+    /// # Returns
+    /// * `Interval` - The simple form of the interval
+    pub fn simple(&self) -> Interval {
+        match self {
+            Interval::FlatNinth => Interval::MinorSecond,
+            Interval::Ninth => Interval::MajorSecond,
+            Interval::SharpNinth => Interval::MinorThird,
+            Interval::Eleventh => Interval::PerfectFourth,
+            Interval::SharpEleventh => Interval::AugmentedFourth,
+            Interval::FlatThirteenth => Interval::MinorSixth,
+            Interval::Thirteenth => Interval::MajorSixth,
+            i => *i,
+        }
+    }
+
     /// Transforms given interval into its chord notation form
     /// # Arguments
     /// * `self` - The interval
@@ -170,9 +280,22 @@ impl Serialize for Interval {
     }
 }
 
+impl<'de> Deserialize<'de> for Interval {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        // Chord notation writes the major seventh as Maj7, while the parser reads it lowercased
+        let notation = if s == "Maj7" { "maj7" } else { s.as_str() };
+        Interval::from_chord_notation(notation)
+            .ok_or_else(|| de::Error::custom(format!("unknown interval {:?}", s)))
+    }
+}
+
 /// Enum representing semantic intervals, meaning that every interval can be any of its possible values.  
 /// It is used to calculate the correct enharmonic notes from given root.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum SemInterval {
     Root = 1,
     Second = 2,
@@ -196,3 +319,214 @@ impl SemInterval {
         *self as u8
     }
 }
+
+/// The direction to move by an interval, see [Chord::transpose_by_interval](super::Chord::transpose_by_interval).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub enum Direction {
+    #[default]
+    Up,
+    Down,
+}
+
+/// A set of intervals, stored as a bitset with one bit per [Interval] variant.
+/// It is cheap to copy, and set operations like unions and intersections are single bitwise operations,
+/// so it is meant for comparing many chords (see [Chord::interval_set](super::Chord::interval_set)).
+/// Intervals are different variants even when they have the same semitones, so a sharp ninth is not a minor third.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct IntervalSet(u32);
+
+impl IntervalSet {
+    /// Creates an empty set.
+    pub fn new() -> IntervalSet {
+        IntervalSet(0)
+    }
+
+    /// Creates a set from its bits, as returned by [IntervalSet::bits].
+    /// # Arguments
+    /// * `bits` - The bits of the set, the `n`-th bit being the `n`-th [Interval] variant.
+    /// # Returns
+    /// * The set, or None if any bit does not match an interval.
+    pub fn from_bits(bits: u32) -> Option<IntervalSet> {
+        (bits >> INTERVALS.len() == 0).then_some(IntervalSet(bits))
+    }
+
+    /// Returns the bits of the set, the `n`-th bit being the `n`-th [Interval] variant.
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Adds an interval to the set.
+    pub fn insert(&mut self, interval: Interval) {
+        self.0 |= 1 << interval as u8;
+    }
+
+    /// Removes an interval from the set.
+    pub fn remove(&mut self, interval: Interval) {
+        self.0 &= !(1 << interval as u8);
+    }
+
+    /// Checks whether the set has the given interval.
+    pub fn contains(&self, interval: Interval) -> bool {
+        self.0 & 1 << interval as u8 != 0
+    }
+
+    /// Returns the intervals in either set.
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet(self.0 | other.0)
+    }
+
+    /// Returns the intervals in both sets.
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet(self.0 & other.0)
+    }
+
+    /// Returns the intervals of this set that are not in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet(self.0 & !other.0)
+    }
+
+    /// Returns the number of intervals in the set.
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Checks whether the set has no intervals.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the intervals of the set in declaration order, which sorts them by semitones.
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        INTERVALS.iter().copied().filter(|i| self.contains(*i))
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = Interval>>(iter: T) -> Self {
+        let mut set = IntervalSet::new();
+        iter.into_iter().for_each(|i| set.insert(i));
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{Interval, IntervalSet, INTERVALS};
+
+    #[test_case(Interval::FlatNinth, Interval::MinorSecond)]
+    #[test_case(Interval::Ninth, Interval::MajorSecond)]
+    #[test_case(Interval::SharpNinth, Interval::MinorThird)]
+    #[test_case(Interval::Eleventh, Interval::PerfectFourth)]
+    #[test_case(Interval::SharpEleventh, Interval::AugmentedFourth)]
+    #[test_case(Interval::FlatThirteenth, Interval::MinorSixth)]
+    #[test_case(Interval::Thirteenth, Interval::MajorSixth)]
+    fn compound(interval: Interval, expected: Interval) {
+        assert!(interval.is_compound());
+        assert!(!expected.is_compound());
+        assert_eq!(interval.simple(), expected);
+        assert_eq!(interval.st() - 12, expected.st());
+    }
+
+    #[test]
+    fn just_ratios_are_close_to_equal_temperament() {
+        for interval in INTERVALS {
+            let (n, d) = interval.just_ratio();
+            let cents = 1200.0 * (n as f64 / d as f64).log2();
+            assert!((cents - 100.0 * interval.st() as f64).abs() < 30.0);
+            if interval.is_compound() {
+                let (sn, sd) = interval.simple().just_ratio();
+                assert_eq!(n * sd, 2 * sn * d);
+            }
+        }
+    }
+
+    #[test_case(Interval::Unison)]
+    #[test_case(Interval::MinorThird)]
+    #[test_case(Interval::AugmentedFifth)]
+    #[test_case(Interval::DiminishedSeventh)]
+    #[test_case(Interval::MajorSeventh)]
+    #[test_case(Interval::Octave)]
+    fn simple(interval: Interval) {
+        assert!(!interval.is_compound());
+        assert_eq!(interval.simple(), interval);
+    }
+
+    #[test_case(Interval::Unison, "\"1\"")]
+    #[test_case(Interval::FlatNinth, "\"b9\"")]
+    #[test_case(Interval::DiminishedSeventh, "\"bb7\"")]
+    #[test_case(Interval::MajorSeventh, "\"Maj7\"")]
+    #[test_case(Interval::SharpEleventh, "\"#11\"")]
+    #[test_case(Interval::Thirteenth, "\"13\"")]
+    fn serialization(interval: Interval, expected: &str) {
+        let json = serde_json::to_string(&interval).unwrap();
+        assert_eq!(json, expected);
+        assert_eq!(serde_json::from_str::<Interval>(&json).unwrap(), interval);
+    }
+
+    #[test]
+    fn deserialization_errors() {
+        assert!(serde_json::from_str::<Interval>("\"FlatNinth\"").is_err());
+        assert!(serde_json::from_str::<Interval>("\"b4\"").is_err());
+        assert!(serde_json::from_str::<Interval>("9").is_err());
+    }
+
+    #[test]
+    fn interval_set() {
+        let c7 = [
+            Interval::Unison,
+            Interval::MajorThird,
+            Interval::PerfectFifth,
+            Interval::MinorSeventh,
+        ]
+        .into_iter()
+        .collect::<IntervalSet>();
+        let cm9 = [
+            Interval::Ninth,
+            Interval::Unison,
+            Interval::MinorThird,
+            Interval::PerfectFifth,
+            Interval::MinorSeventh,
+        ]
+        .into_iter()
+        .collect::<IntervalSet>();
+        assert_eq!(c7.len(), 4);
+        assert!(c7.contains(Interval::MinorSeventh));
+        assert!(!c7.contains(Interval::SharpNinth));
+        assert_eq!(
+            c7.intersection(&cm9).iter().collect::<Vec<_>>(),
+            vec![
+                Interval::Unison,
+                Interval::PerfectFifth,
+                Interval::MinorSeventh
+            ]
+        );
+        assert_eq!(c7.union(&cm9).len(), 6);
+        assert_eq!(
+            cm9.difference(&c7).iter().collect::<Vec<_>>(),
+            vec![Interval::MinorThird, Interval::Ninth]
+        );
+        let mut set = c7;
+        set.remove(Interval::MajorThird);
+        set.remove(Interval::MajorThird);
+        set.insert(Interval::SharpNinth);
+        assert_eq!(set.len(), 4);
+        assert!(!set.contains(Interval::MinorThird));
+        assert!(IntervalSet::new().is_empty());
+    }
+
+    #[test_case(0, true)]
+    #[test_case(0x111, true)]
+    #[test_case(0x7F_FFFF, true)]
+    #[test_case(0x80_0000, false)]
+    #[test_case(u32::MAX, false)]
+    fn interval_set_from_bits(bits: u32, valid: bool) {
+        let set = IntervalSet::from_bits(bits);
+        assert_eq!(set.is_some(), valid);
+        if let Some(set) = set {
+            assert_eq!(set.bits(), bits);
+            assert_eq!(set.iter().collect::<IntervalSet>(), set);
+        }
+    }
+}
@@ -1,27 +1,45 @@
 //! # Chords, notes and intervals
 use std::vec;
 
-use intervals::{Interval, SemInterval};
-use normalize::normalize;
+use intervals::{Direction, Interval, IntervalSet, SemInterval};
+use normalize::{normalize, normalize_with, NamingStyle};
 use quality::{InnerQuality, Quality};
 use serde::{Deserialize, Serialize};
 use serde_json;
 
-use note::Note;
+use crate::{
+    parsing::Parser,
+    scales::{major_scale, suggested_scale},
+};
+
+use note::{Modifier, Note, NoteRole};
 
 pub mod intervals;
-pub(crate) mod normalize;
+pub mod normalize;
 pub mod note;
+pub mod presets;
 pub mod quality;
 
+/// The MIDI octave of the root of a chord unless changed, which puts the root of C at 48.
+const DEFAULT_OCTAVE: u8 = 3;
+/// Lowest and highest octaves for the root, which keep all the MIDI codes of a chord in range.
+const MIN_OCTAVE: u8 = 1;
+const MAX_OCTAVE: u8 = 8;
+
 /// Chord representation of a successfully parsed string.
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct Chord {
     /// The string that originated the chord.
     pub origin: String,
     /// The descriptor of the chord (all beyond its root).
     pub descriptor: String,
-    /// Normalized input
+    /// Normalized input.
+    /// Equivalent spellings share it: an augmented fifth from `+` or from `#5` is always written as an alteration,
+    /// so both `C+Maj7` and `CMaj7#5` are `CMaj7(#5)`.
+    /// Likewise, half-diminished chords are always written as minor sevenths with a flat fifth,
+    /// so `Cø`, `Cø7`, `Cm7b5` and `Cmi7(b5)` are all `Cmin7(b5)`.
+    /// Alterations are written in ascending order of pitch whatever their order in the input,
+    /// so `C7#9#5`, `C7#5#9` and `C7(#9,#5)` are all `C7(#5,#9)`.
     pub normalized: String,
     /// The root note of the chord.
     pub root: Note,
@@ -50,6 +68,27 @@ pub struct Chord {
     adds: Vec<Interval>,
     #[serde(skip_serializing)]
     rbs: [bool; 24],
+    /// Adds from the input string whose interval was already in the chord.
+    #[serde(skip_serializing)]
+    redundant_adds: Vec<Interval>,
+    /// The MIDI octave of the root, used to place the MIDI codes.
+    #[serde(skip_serializing)]
+    octave: u8,
+}
+
+/// The differences between two chords, see [Chord::diff].
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct ChordDiff {
+    /// Intervals of the new chord that the old one lacks.
+    pub added: Vec<Interval>,
+    /// Intervals of the old chord that the new one lacks.
+    pub removed: Vec<Interval>,
+    /// Whether the root note changed, spelling included.
+    pub root_changed: bool,
+    /// Whether the slash-bass changed, spelling included, which is also the case when one of the chords has none.
+    pub bass_changed: bool,
+    /// Whether the [Quality] changed.
+    pub quality_changed: bool,
 }
 
 impl Chord {
@@ -57,17 +96,151 @@ impl Chord {
         ChordBuilder::new(origin, root)
     }
 
+    /// Builds a chord from its root and the intervals of its notes, like the formulas in [presets].
+    /// The root is added if `intervals` lacks it, and duplicated intervals are ignored.
+    /// A chord with no third but a fourth or an eleventh is taken as sus.
+    /// Since there is no input to parse, both the origin and the descriptor come from the normalized name.
+    /// # Arguments
+    /// * `root` - The root note of the chord.
+    /// * `intervals` - The intervals of the chord relative to its root.
+    /// # Returns
+    /// * The chord those intervals form.
+    pub fn from_root_and_intervals(root: Note, intervals: &[Interval]) -> Chord {
+        let mut real_intervals = vec![Interval::Unison];
+        for i in intervals {
+            if !real_intervals.contains(i) {
+                real_intervals.push(*i);
+            }
+        }
+        real_intervals.sort_by_key(|i| i.st());
+
+        let mut rbs = [false; 24];
+        let mut semitones = Vec::new();
+        let mut semantic_intervals = Vec::new();
+        let mut notes = Vec::new();
+        for i in &real_intervals {
+            rbs[i.st() as usize] = true;
+            semitones.push(i.st());
+            semantic_intervals.push(i.to_semantic_interval().numeric());
+            notes.push(root.get_note(i.st(), i.to_semantic_interval().numeric()));
+        }
+        let has_sem = |sem: SemInterval| semantic_intervals.contains(&sem.numeric());
+        let is_sus = !has_sem(SemInterval::Third)
+            && (has_sem(SemInterval::Fourth) || has_sem(SemInterval::Eleventh));
+        let note_literals = notes.iter().map(|n| n.to_string()).collect();
+
+        let mut chord = Chord::builder("", root)
+            .notes(notes)
+            .note_literals(note_literals)
+            .rbs(rbs)
+            .semitones(semitones)
+            .semantic_intervals(semantic_intervals)
+            .real_intervals(real_intervals)
+            .is_sus(is_sus)
+            .build();
+        chord.origin = chord.normalized.clone();
+        chord.descriptor = chord.normalized_descriptor();
+        chord
+    }
+
+    /// Builds a chord from its root and a set of pitch classes (0 for C, 1 for C#/Db and so on up to 11 for B).
+    /// Pitch classes carry no spelling nor function, so the root must be given: the same set is `C6` with C as root
+    /// and `Amin7` with A as root. The root is added if `pcs` lacks it, and values above 11 are taken modulo 12.
+    ///
+    /// Each pitch class is taken as the interval it most commonly is in a chord given the rest of the set:
+    /// - 3 semitones are a minor third, or a sharp ninth if there is also a major third.
+    /// - 5 semitones are an eleventh over a third, or a sus fourth otherwise.
+    /// - 6 semitones are a sharp eleventh over a perfect fifth, or a flat fifth otherwise.
+    /// - 8 semitones are a flat thirteenth over a perfect fifth, or a sharp fifth otherwise.
+    /// - 9 semitones are a diminished seventh in a diminished triad with no other seventh,
+    ///   a thirteenth over a seventh, or a sixth otherwise.
+    /// - 1 and 2 semitones are always ninths (sus2 chords are written as chords with a ninth and no third).
+    ///
+    /// Then the chord is built as in [Chord::from_root_and_intervals].
+    /// # Arguments
+    /// * `root` - The root note of the chord.
+    /// * `pcs` - The pitch classes of the notes of the chord.
+    /// # Returns
+    /// * The chord those pitch classes form over the root.
+    pub fn from_pitch_classes(root: Note, pcs: &[u8]) -> Chord {
+        let mut relative = [false; 12];
+        relative[0] = true;
+        for pc in pcs {
+            relative[((pc % 12) + 12 - root.to_semitone()) as usize % 12] = true;
+        }
+        let has = |st: usize| relative[st];
+        let has_third = has(3) || has(4);
+        let intervals = (1..12)
+            .filter(|st| has(*st))
+            .map(|st| match st {
+                1 => Interval::FlatNinth,
+                2 => Interval::Ninth,
+                3 if has(4) => Interval::SharpNinth,
+                3 => Interval::MinorThird,
+                4 => Interval::MajorThird,
+                5 if has_third => Interval::Eleventh,
+                5 => Interval::PerfectFourth,
+                6 if has(7) => Interval::SharpEleventh,
+                6 => Interval::DiminishedFifth,
+                7 => Interval::PerfectFifth,
+                8 if has(7) => Interval::FlatThirteenth,
+                8 => Interval::AugmentedFifth,
+                9 if has(3) && has(6) && !has(7) && !has(10) && !has(11) => {
+                    Interval::DiminishedSeventh
+                }
+                9 if has(10) || has(11) => Interval::Thirteenth,
+                9 => Interval::MajorSixth,
+                10 => Interval::MinorSeventh,
+                _ => Interval::MajorSeventh,
+            })
+            .collect::<Vec<Interval>>();
+        Chord::from_root_and_intervals(root, &intervals)
+    }
+
+    /// Finds the root of a slash chord from its bass and a descriptor saying which degree of the chord the bass is.
+    /// The descriptor is written as in [Parser::parse_descriptor](crate::parsing::Parser::parse_descriptor),
+    /// followed by a slash and the degree of the bass, so `E` with `7/3` gives `C` (as in `C7/E`),
+    /// and `Bb` with `m7/5` gives `Eb` (as in `Ebm7/Bb`). The root is spelled from the bass by that interval.
+    /// # Arguments
+    /// * `bass` - The bass note of the chord.
+    /// * `descriptor` - The descriptor of the chord followed by the degree of the bass, like `maj7/3`.
+    /// # Returns
+    /// * The root of the chord, or None if it is not determined: the descriptor is not valid, it has no degree
+    ///   (`6/9` is a six-nine chord with no degree), or the chord has none or several notes of the degree
+    ///   (like the fifths of `7(b5,#5)`).
+    pub fn root_from_bass(bass: &Note, descriptor: &str) -> Option<Note> {
+        let (quality, degree) = descriptor.rsplit_once('/')?;
+        let degree = degree.trim().parse::<u8>().ok()?;
+        let chord = Parser::new()
+            .parse_descriptor(bass.clone(), quality)
+            .ok()
+            .filter(|chord| chord.bass.is_none())?;
+        let mut matching = chord
+            .real_intervals
+            .iter()
+            .filter(|i| i.to_semantic_interval().numeric() == degree);
+        let interval = matching.next()?;
+        if matching.next().is_some() {
+            return None;
+        }
+        let st = (12 - interval.st() % 12) % 12;
+        let degree = (7 - (degree - 1) % 7) % 7;
+        Some(bass.get_note(st, degree + 1))
+    }
+
     /// Transposes the chord to a different root note.
+    /// A slash bass keeps its letter distance to the root, so `G/B` transposed to A is `A/C#`, not `A/Db`.
     /// # Arguments
     /// * `self` - The chord to transpose.
     /// * `transpose_to` - The note to transpose the chord to.
     /// # Returns
     /// * A new chord transposed to the new root note.
     pub fn transpose_to(&self, transpose_to: &Note) -> Chord {
-        let bass = self
-            .bass
-            .as_ref()
-            .map(|bass| self.root.transpose_to(bass, transpose_to));
+        let bass = self.bass.as_ref().map(|bass| {
+            let st = (bass.to_semitone() + 12 - self.root.to_semitone()) % 12;
+            let letters = (bass.literal.numeric() + 7 - self.root.literal.numeric()) % 7;
+            transpose_to.get_note(st, letters + 1)
+        });
 
         let mut notes = Vec::new();
         let semitones = self.semitones.clone();
@@ -99,31 +272,1121 @@ impl Chord {
             .semitones(semitones)
             .semantic_intervals(semantic_intervals)
             .real_intervals(self.real_intervals.clone())
+            .rbs(self.rbs)
             .adds(self.adds.clone())
+            .redundant_adds(self.redundant_adds.clone())
             .is_sus(self.is_sus)
+            .octave(self.octave)
             .build()
     }
 
+    /// Returns the normalized name of the chord transposed to a different root note, like `Dmin7(b5)` for `Cm7b5` to D.
+    /// Unlike the origin of [Chord::transpose_to], which respells the input descriptor over the new root,
+    /// the name is built from the notes of the chord, so it can always be parsed back into the same chord.
+    /// # Arguments
+    /// * `self` - The chord to transpose.
+    /// * `to` - The note to transpose the chord to.
+    /// # Returns
+    /// * The normalized name of the transposed chord.
+    pub fn transposed_symbol(&self, to: &Note) -> String {
+        self.transpose_to(to).normalized
+    }
+
+    /// Transposes the chord to a different root note placed in the given MIDI octave, where C4 is the central C (60).
+    /// The note gives the spelling and the octave gives the register, so the MIDI codes of the result
+    /// (see [Chord::to_midi_codes]) are built from that root: `G7` transposed to D in octave 5 has its root at 74.
+    /// The octave follows the letter of the note, so B#4 is 72 and Cb4 is 59.
+    /// # Arguments
+    /// * `self` - The chord to transpose.
+    /// * `transpose_to` - The note to transpose the chord to.
+    /// * `octave` - The octave of the new root, clamped from 1 to 8.
+    /// # Returns
+    /// * A new chord transposed to the new root note.
+    pub fn transpose_to_octave(&self, transpose_to: &Note, octave: u8) -> Chord {
+        let mut chord = self.transpose_to(transpose_to);
+        chord.octave = octave.clamp(MIN_OCTAVE, MAX_OCTAVE);
+        chord
+    }
+
+    /// Returns the MIDI octave of the root, where C4 is the central C (60).
+    /// It is 3 unless set with [Chord::transpose_to_octave].
+    /// # Arguments
+    /// * `self` - The chord to get the octave from.
+    /// # Returns
+    /// * The octave of the root.
+    pub fn octave(&self) -> u8 {
+        self.octave
+    }
+
+    /// Transposes the chord by a number of semitones, up if positive and down if negative.
+    /// The new root is spelled without accidentals when possible. Otherwise it is spelled with a sharp if the current
+    /// root has sharps and with a flat if not, so `C` up one semitone gives `Db` while `C#` up two gives `D#`.
+    /// # Arguments
+    /// * `self` - The chord to transpose.
+    /// * `semitones` - The number of semitones to transpose the chord by.
+    /// # Returns
+    /// * A new chord transposed by the given semitones.
+    pub fn transpose_by_semitones(&self, semitones: i8) -> Chord {
+        let diff = (semitones as i16).rem_euclid(12) as u8;
+        let candidates = self.root.literal.get_matcher(self.root.to_semitone(), diff);
+        let prefers_sharps = matches!(
+            self.root.modifier,
+            Some(Modifier::Sharp) | Some(Modifier::DSharp)
+        );
+        let (literal, modifier) = candidates
+            .iter()
+            .find(|(_, m)| m.is_none())
+            .or_else(|| {
+                candidates
+                    .iter()
+                    .find(|(_, m)| prefers_sharps && *m == Some(Modifier::Sharp))
+            })
+            .unwrap_or(&candidates[0])
+            .to_owned();
+        self.transpose_to(&Note::new(literal, modifier))
+    }
+
+    /// Transposes the chord by an interval, so the root moves by its degree as well as by its semitones.
+    /// Unlike [Chord::transpose_by_semitones], the spelling follows from the interval: `C` up an augmented fourth
+    /// is `F#` while up a diminished fifth is `Gb`. Compound intervals move like their simple counterparts,
+    /// so a ninth is a second, and moving down by an interval is moving up by its inversion.
+    /// # Arguments
+    /// * `self` - The chord to transpose.
+    /// * `interval` - The interval to transpose the chord by.
+    /// * `direction` - Whether to move the root up or down.
+    /// # Returns
+    /// * A new chord transposed by the given interval.
+    pub fn transpose_by_interval(&self, interval: Interval, direction: Direction) -> Chord {
+        let st = interval.st() % 12;
+        let degree = (interval.to_semantic_interval().numeric() - 1) % 7;
+        let (st, degree) = match direction {
+            Direction::Up => (st, degree),
+            Direction::Down => ((12 - st) % 12, (7 - degree) % 7),
+        };
+        self.transpose_to(&self.root.get_note(st, degree + 1))
+    }
+
+    /// Returns the chord with its root spelled the other common way, so `C#7` gives `Db7` and `Db7` gives `C#7`.
+    /// The rest of the notes, and the slash-bass if any, are spelled again from the new root.
+    /// See [Note::enharmonic] for the available spellings.
+    /// # Arguments
+    /// * `self` - The chord to respell.
+    /// # Returns
+    /// * The respelled chord, or None if the root has no other spelling with a single accidental at most.
+    pub fn respell_root(&self) -> Option<Chord> {
+        self.root.enharmonic().map(|root| self.transpose_to(&root))
+    }
+
+    /// Returns the tritone substitute of a dominant chord, which is the dominant a tritone away (`G7` gives `Db7`).
+    /// All the extensions and alterations of the chord are kept, and so is the slash-bass relative to the root.
+    /// The new root is spelled a diminished fifth above, or an augmented fourth if that would need a double accidental
+    /// (so `Db7` gives `G7` instead of `A𝄫7`).
+    /// # Arguments
+    /// * `self` - The chord to substitute.
+    /// # Returns
+    /// * The substitute chord, or None if the chord is not a dominant with a major third.
+    pub fn tritone_sub(&self) -> Option<Chord> {
+        if self.quality != Quality::Dominant || !self.has(Interval::MajorThird) {
+            return None;
+        }
+        let tritone = Interval::DiminishedFifth.st();
+        let mut root = self.root.get_note(tritone, SemInterval::Fifth.numeric());
+        if matches!(
+            root.modifier,
+            Some(Modifier::DFlat) | Some(Modifier::DSharp)
+        ) {
+            root = self.root.get_note(tritone, SemInterval::Fourth.numeric());
+        }
+        Some(self.transpose_to(&root))
+    }
+
+    /// Returns the normalized name of the chord written in the given style.
+    /// # Arguments
+    /// * `self` - The chord to name.
+    /// * `style` - The naming style, see [NamingStyle].
+    /// # Returns
+    /// * The normalized name of the chord.
+    pub fn normalized_with(&self, style: NamingStyle) -> String {
+        normalize_with(self, style)
+    }
+
+    /// Returns a copy of the chord without its slash-bass note.
+    /// # Arguments
+    /// * `self` - The chord to remove the bass from.
+    /// # Returns
+    /// * A new chord with no bass and its normalized name recomputed.
+    pub fn without_bass(&self) -> Chord {
+        let mut chord = self.clone();
+        chord.bass = None;
+        chord.normalized = normalize(&chord);
+        chord
+    }
+
+    /// Returns a copy of the chord with given note as slash-bass, replacing the current one if any.
+    /// # Arguments
+    /// * `self` - The chord to add the bass to.
+    /// * `note` - The bass note.
+    /// # Returns
+    /// * A new chord with the bass set and its normalized name recomputed.
+    pub fn with_bass(&self, note: Note) -> Chord {
+        let mut chord = self.clone();
+        chord.bass = Some(note);
+        chord.normalized = normalize(&chord);
+        chord
+    }
+
+    /// Returns a copy of the chord without the notes of given degree, like the omit modifier does.
+    /// Every note of the degree is removed, so omitting the fifth of `C7(b5,#5)` removes both alterations.
+    /// The root cannot be omitted, so omitting it returns the same chord.
+    /// # Arguments
+    /// * `self` - The chord to edit.
+    /// * `degree` - The degree to omit.
+    /// # Returns
+    /// * A new chord without the degree, with its notes and normalized name recomputed.
+    pub fn with_omitted(&self, degree: SemInterval) -> Chord {
+        if degree == SemInterval::Root {
+            return self.clone();
+        }
+        let intervals = self
+            .real_intervals
+            .iter()
+            .filter(|i| i.to_semantic_interval() != degree)
+            .copied()
+            .collect();
+        self.with_intervals(intervals, self.adds.clone())
+    }
+
+    /// Returns a copy of the chord with given interval added, like the add modifier does.
+    /// Nothing but the interval is added, so adding a ninth to a triad does not imply a seventh.
+    /// If the chord already has the interval, the same chord is returned.
+    /// # Arguments
+    /// * `self` - The chord to edit.
+    /// * `interval` - The interval to add.
+    /// # Returns
+    /// * A new chord with the interval, with its notes and normalized name recomputed.
+    pub fn with_added(&self, interval: Interval) -> Chord {
+        if self.real_intervals.contains(&interval) {
+            return self.clone();
+        }
+        let mut intervals = self.real_intervals.clone();
+        intervals.push(interval);
+        let mut adds = self.adds.clone();
+        adds.push(interval);
+        self.with_intervals(intervals, adds)
+    }
+
+    /// Returns the triad of the chord, keeping its root, third and fifth and dropping sevenths, sixths and tensions.
+    /// Altered fifths are kept, so `Cm7b5` gives `Cdim` and `C7(#5)` gives `C(#5)`.
+    /// Chords with no third keep their sus tone instead, so `C9sus` gives `Csus` and `C7sus2` gives `Csus2`
+    /// (written `C(add9,omit3)`, since sus2 chords are stored with a ninth).
+    /// The slash-bass, if any, is kept.
+    /// # Arguments
+    /// * `self` - The chord to simplify.
+    /// # Returns
+    /// * A new chord with the triad, with its notes and normalized name recomputed.
+    pub fn triad(&self) -> Chord {
+        let mut intervals = self
+            .real_intervals
+            .iter()
+            .filter(|i| {
+                matches!(
+                    i.to_semantic_interval(),
+                    SemInterval::Root | SemInterval::Third | SemInterval::Fifth
+                )
+            })
+            .copied()
+            .collect::<Vec<Interval>>();
+        if !self.has_sem(SemInterval::Third) {
+            let sus = [
+                (Interval::PerfectFourth, Interval::PerfectFourth),
+                (Interval::Eleventh, Interval::PerfectFourth),
+                (Interval::SharpEleventh, Interval::SharpEleventh),
+                (Interval::Ninth, Interval::Ninth),
+                (Interval::FlatNinth, Interval::FlatNinth),
+            ]
+            .into_iter()
+            .find(|(i, _)| self.real_intervals.contains(i));
+            if let Some((_, tone)) = sus {
+                intervals.push(tone);
+            }
+        }
+        self.with_intervals(intervals, Vec::new())
+    }
+
+    /// Returns the shell of the chord: its root, its third and its seventh, which are the notes defining its quality.
+    /// Chords with no third use their sus tone instead (the fourth, or the ninth for sus2 chords),
+    /// and chords with no seventh give just the root and the third.
+    /// See [Chord::seventh] for chords with more than one seventh.
+    /// # Arguments
+    /// * `self` - The chord to get the shell from.
+    /// # Returns
+    /// * The notes of the shell, from the root up.
+    pub fn shell(&self) -> Vec<Note> {
+        let third = [
+            Interval::MinorThird,
+            Interval::MajorThird,
+            Interval::PerfectFourth,
+            Interval::Eleventh,
+            Interval::SharpEleventh,
+            Interval::Ninth,
+            Interval::FlatNinth,
+        ]
+        .into_iter()
+        .find(|i| self.real_intervals.contains(i));
+        [Some(Interval::Unison), third, self.seventh()]
+            .into_iter()
+            .flatten()
+            .filter_map(|i| self.real_intervals.iter().position(|r| *r == i))
+            .map(|idx| self.notes[idx].clone())
+            .collect()
+    }
+
+    /// Rebuilds the chord from a new set of intervals, keeping its root, bass and input strings.
+    fn with_intervals(&self, mut real_intervals: Vec<Interval>, adds: Vec<Interval>) -> Chord {
+        real_intervals.sort_by_key(|i| i.st());
+        let mut rbs = [false; 24];
+        let mut semitones = Vec::new();
+        let mut semantic_intervals = Vec::new();
+        let mut notes = Vec::new();
+        for i in &real_intervals {
+            rbs[i.st() as usize] = true;
+            semitones.push(i.st());
+            semantic_intervals.push(i.to_semantic_interval().numeric());
+            notes.push(
+                self.root
+                    .get_note(i.st(), i.to_semantic_interval().numeric()),
+            );
+        }
+        let has_sem = |sem: SemInterval| semantic_intervals.contains(&sem.numeric());
+        let is_sus = self.is_sus
+            && !has_sem(SemInterval::Third)
+            && (has_sem(SemInterval::Fourth) || has_sem(SemInterval::Eleventh));
+        let note_literals = notes.iter().map(|n| n.to_string()).collect();
+
+        Chord::builder(&self.origin, self.root.clone())
+            .descriptor(&self.descriptor)
+            .bass(self.bass.clone())
+            .notes(notes)
+            .note_literals(note_literals)
+            .semitones(semitones)
+            .semantic_intervals(semantic_intervals)
+            .real_intervals(real_intervals)
+            .rbs(rbs)
+            .adds(adds)
+            .redundant_adds(self.redundant_adds.clone())
+            .is_sus(is_sus)
+            .octave(self.octave)
+            .build()
+    }
+
+    /// Returns the notes of the chord ordered as a stack of thirds from the root, if the chord is tertian.
+    /// Seconds, fourths and sixths are taken as their compound forms (9, 11 and 13), so `C6` gives C, E, G, A.
+    /// Notes sharing a degree (like b5 and #5) are ordered by semitone.
+    /// # Arguments
+    /// * `self` - The chord to stack.
+    /// # Returns
+    /// * The stacked notes, or None if the chord has no third (sus, power and omit3 chords).
+    pub fn stacked_thirds(&self) -> Option<Vec<Note>> {
+        if self.is_sus || !self.has_sem(SemInterval::Third) {
+            return None;
+        }
+        let mut stack: Vec<(u8, u8, &Note)> = self
+            .semantic_intervals
+            .iter()
+            .zip(&self.semitones)
+            .zip(&self.notes)
+            .map(|((sem, st), note)| {
+                let degree = match *sem {
+                    2 => 9,
+                    4 => 11,
+                    6 => 13,
+                    d => d,
+                };
+                (degree, *st, note)
+            })
+            .collect();
+        stack.sort_by_key(|(degree, st, _)| (*degree, *st));
+        Some(stack.into_iter().map(|(_, _, note)| note.clone()).collect())
+    }
+
+    /// Returns the chord in Nashville number system notation relative to the major key of `key`.
+    /// The number is the degree of the root in the key, and the descriptor is the normalized one with
+    /// `-` for minor, `△` for major seventh and `°` for diminished (so Dm7 in C is `2-7` and CmMaj7 is `1-△7`).
+    /// Digits that start the descriptor are written as superscripts, so that they do not read as part of the degree:
+    /// G7 in C is `5⁷` rather than `57`, and C69 is `1⁶⁹`.
+    /// Slash basses are written as degrees too (C/E in C is `1/3`).
+    ///
+    /// Non diatonic roots are numbered after their letter and prefixed with the needed accidentals,
+    /// so in C a `Db` root is `b2` while a `C#` root is `#1`.
+    /// # Arguments
+    /// * `self` - The chord to number.
+    /// * `key` - The tonic of the major key.
+    /// # Returns
+    /// * The Nashville notation of the chord.
+    pub fn to_nashville(&self, key: &Note) -> String {
+        let number = |note: &Note| {
+            let (degree, alteration) = note.scale_degree(key);
+            let accidental = if alteration < 0 { "b" } else { "#" };
+            format!(
+                "{}{}",
+                accidental.repeat(alteration.unsigned_abs() as usize),
+                degree
+            )
+        };
+        let descriptor = self
+            .normalized_descriptor()
+            .replace("min", "-")
+            .replace("Maj", "△")
+            .replace("dim", "°");
+        let digits = descriptor
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(descriptor.len());
+        let superscript = descriptor[..digits]
+            .chars()
+            .map(|c| ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'][c as usize - '0' as usize])
+            .collect::<String>();
+        let mut res = format!(
+            "{}{}{}",
+            number(&self.root),
+            superscript,
+            &descriptor[digits..]
+        );
+        if let Some(bass) = &self.bass {
+            res.push('/');
+            res.push_str(&number(bass));
+        }
+        res
+    }
+
+    /// Checks if two chords commonly substitute for each other, following these rules:
+    /// - Relative major and minor: a major chord and the minor chord a major sixth above its root (`C` and `Am`).
+    /// - Tritone substitution: two dominant chords a tritone apart (`G7` and `Db7`).
+    /// - ii for V: a dominant chord and the minor chord a fifth above its root (`G7` and `Dm7`).
+    ///
+    /// Roots are compared by pitch class, and the rules are symmetric, so `a.is_substitute_for(b)` equals `b.is_substitute_for(a)`.
+    /// # Arguments
+    /// * `self` - The chord to check.
+    /// * `other` - The chord it would substitute.
+    /// # Returns
+    /// * True if any of the rules applies.
+    pub fn is_substitute_for(&self, other: &Chord) -> bool {
+        let distance =
+            |from: &Chord, to: &Chord| (to.root.to_semitone() + 12 - from.root.to_semitone()) % 12;
+        let relative = |major: &Chord, minor: &Chord| {
+            major.quality == Quality::Major
+                && minor.quality == Quality::Minor
+                && distance(major, minor) == 9
+        };
+        let ii_for_v = |dominant: &Chord, minor: &Chord| {
+            dominant.quality == Quality::Dominant
+                && minor.quality == Quality::Minor
+                && distance(dominant, minor) == 7
+        };
+        let tritone = self.quality == Quality::Dominant
+            && other.quality == Quality::Dominant
+            && distance(self, other) == 6;
+
+        tritone
+            || relative(self, other)
+            || relative(other, self)
+            || ii_for_v(self, other)
+            || ii_for_v(other, self)
+    }
+
+    /// Returns a user-facing name for the type of the chord, like `Dominant 7th`, `Minor/Major 7th` or `Half-diminished`.
+    /// This is finer than [Quality::display_name]: sixths and sevenths are told apart, and
+    /// half-diminished, diminished 7th and augmented chords get their own names.
+    /// # Arguments
+    /// * `self` - The chord to name.
+    /// # Returns
+    /// * The name of the chord type.
+    pub fn quality_name(&self) -> &'static str {
+        let b5 = self.has(Interval::DiminishedFifth) && !self.has(Interval::PerfectFifth);
+        match self.complete_quality {
+            InnerQuality::Minor7 if b5 => "Half-diminished",
+            InnerQuality::Diminished if self.has(Interval::DiminishedSeventh) => "Diminished 7th",
+            InnerQuality::Major if self.quality == Quality::Augmented => "Augmented",
+            _ => self.complete_quality.display_name(),
+        }
+    }
+
+    /// Checks whether this chord works as the dominant of `tonic`: a chord of dominant quality
+    /// (`G7`, `G9`, `G7sus`, `G7alt`...) whose root is a perfect fifth above the tonic.
+    /// Only the local relationship is checked, so secondary dominants are detected as well (`D7` resolves to `G`).
+    /// The slash-bass is ignored and roots are compared by pitch class.
+    /// # Arguments
+    /// * `self` - The chord to check.
+    /// * `tonic` - The note the chord would resolve to.
+    /// # Returns
+    /// * True if the chord is a dominant a fifth above `tonic`.
+    pub fn resolves_to(&self, tonic: &Note) -> bool {
+        self.quality == Quality::Dominant
+            && (self.root.to_semitone() + 12 - tonic.to_semitone()) % 12 == 7
+    }
+
+    /// Returns the notes of the scale that best fits the chord for improvising over it, starting at its root.
+    /// The scale is picked from the quality and the alterations of the chord, with the first matching row of this table:
+    ///
+    /// | Chord | Example | Scale |
+    /// |-------|---------|-------|
+    /// | Dominant with an altered ninth and an altered fifth or b13 | `C7alt`, `C7(#5,#9)` | Altered |
+    /// | Dominant with an altered ninth | `C7(b9)` | Half-whole diminished |
+    /// | Dominant with a #11 | `C9(#11)` | Lydian dominant |
+    /// | Dominant with a #5 | `C7(#5)` | Whole tone |
+    /// | Dominant with a b13 | `C7(b13)` | Mixolydian b13 |
+    /// | Any other dominant, sus included | `C7`, `C13`, `C7sus` | Mixolydian |
+    /// | Minor with a b5 and a 9 | `Cm9(b5)` | Locrian #2 |
+    /// | Minor with a b5 | `Cm7b5` | Locrian |
+    /// | Minor with a major seventh | `CmMaj7` | Melodic minor |
+    /// | Minor with a b9 | `Cm7(b9)` | Phrygian |
+    /// | Minor with a b6 or b13 | `Cm(b6)` | Aeolian |
+    /// | Any other minor | `Cm`, `Cm7`, `Cm6` | Dorian |
+    /// | Diminished | `Cdim`, `Cdim7` | Whole-half diminished |
+    /// | Augmented, or major with a #5 | `C+`, `CMaj7(#5)` | Lydian augmented |
+    /// | Major with a #11 or b5 | `CMaj7(#11)` | Lydian |
+    /// | Any other chord, power chords included | `C`, `CMaj7`, `C6/9`, `C5` | Ionian (major) |
+    ///
+    /// Every note is spelled from the letter of its degree, as in [major_scale], so the altered scale of C is C, Db, Eb, Fb, Gb, Ab, Bb.
+    /// Symmetric scales have more or less than seven notes: the diminished ones have eight and the whole tone one has six.
+    /// The slash-bass is ignored.
+    /// # Arguments
+    /// * `self` - The chord to get the scale for.
+    /// # Returns
+    /// * The notes of the scale in ascending order, starting at the root of the chord.
+    pub fn suggested_scale(&self) -> Vec<Note> {
+        suggested_scale(self)
+    }
+
+    /// Classifies a note by its function over the chord, comparing pitch classes so the spelling does not matter.
+    /// Notes of the chord are chord tones, or tensions if they are extensions (9, 11, 13 and their alterations).
+    /// Any other note is checked against the scale of the chord (see [Chord::suggested_scale]):
+    /// - A note outside the scale is outside, like the major seventh over `C7`.
+    /// - A note of the scale a half step above a chord tone is an avoid note, since it clashes with it
+    ///   as a minor ninth: the 11 over a major third (F over `CMaj7` or `C7`), or the b9 over the root in `Cm7b5`.
+    /// - Any other note of the scale is a tension, like the 9 over `C` or the 11 over `Cm7`.
+    /// # Arguments
+    /// * `self` - The chord to classify the note against.
+    /// * `note` - The note to classify.
+    /// # Returns
+    /// * The role of the note over the chord.
+    pub fn classify_note(&self, note: &Note) -> NoteRole {
+        let pc = note.to_semitone();
+        if let Some((interval, _)) = self
+            .real_intervals
+            .iter()
+            .zip(&self.notes)
+            .find(|(_, n)| n.to_semitone() == pc)
+        {
+            return if interval.is_compound() {
+                NoteRole::Tension
+            } else {
+                NoteRole::ChordTone
+            };
+        }
+        if self.bass.as_ref().is_some_and(|b| b.to_semitone() == pc) {
+            return NoteRole::ChordTone;
+        }
+        if !self.suggested_scale().iter().any(|n| n.to_semitone() == pc) {
+            return NoteRole::Outside;
+        }
+        let mut chord_pcs = self.notes.iter().chain(&self.bass).map(|n| n.to_semitone());
+        if chord_pcs.any(|chord_pc| (chord_pc + 1) % 12 == pc) {
+            NoteRole::Avoid
+        } else {
+            NoteRole::Tension
+        }
+    }
+
+    /// Returns the diatonic chord of the major key of `key` closest to this one.
+    /// Chords with a seventh snap to the diatonic seventh chords, and any other chord to the diatonic triads.
+    ///
+    /// The distance between two chords is the number of pitch classes found in only one of them,
+    /// so the closest chord is the one needing the fewest notes added or removed.
+    /// Ties are broken by preferring a chord with the same root pitch class, and then the lowest degree,
+    /// so `C#m7` in C snaps to `CMaj7` rather than `Emin7`.
+    /// The slash-bass of the chord, if any, is not taken into account and the result has none.
+    /// # Arguments
+    /// * `self` - The chord to snap.
+    /// * `key` - The tonic of the major key.
+    /// # Returns
+    /// * The closest diatonic chord.
+    pub fn snap_to_key(&self, key: &Note) -> Chord {
+        let formulas = if self.has_sem(SemInterval::Seventh) {
+            [
+                presets::MAJOR_7,
+                presets::MINOR_7,
+                presets::MINOR_7,
+                presets::MAJOR_7,
+                presets::DOMINANT_7,
+                presets::MINOR_7,
+                presets::MINOR_7B5,
+            ]
+        } else {
+            [
+                presets::MAJOR_TRIAD,
+                presets::MINOR_TRIAD,
+                presets::MINOR_TRIAD,
+                presets::MAJOR_TRIAD,
+                presets::MAJOR_TRIAD,
+                presets::MINOR_TRIAD,
+                presets::DIMINISHED_TRIAD,
+            ]
+        };
+        let pitch_classes = |notes: &[Note]| {
+            let mut pcs = [false; 12];
+            notes
+                .iter()
+                .for_each(|n| pcs[n.to_semitone() as usize] = true);
+            pcs
+        };
+        let own = pitch_classes(&self.notes);
+        let root = self.root.to_semitone();
+
+        major_scale(key)
+            .into_iter()
+            .zip(formulas)
+            .map(|(note, formula)| Chord::from_root_and_intervals(note, formula))
+            .min_by_key(|candidate| {
+                let other = pitch_classes(&candidate.notes);
+                let distance = own.iter().zip(other).filter(|(a, b)| **a != *b).count();
+                (distance, candidate.root.to_semitone() != root)
+            })
+            .expect("there are always seven diatonic chords")
+    }
+
+    /// Returns the descriptor of the chord as written in the input (all beyond its root), same as the `descriptor` field.
+    /// # Arguments
+    /// * `self` - The chord to get the descriptor from.
+    /// # Returns
+    /// * The descriptor of the input, which includes the slash-bass if any.
+    pub fn descriptor_only(&self) -> &str {
+        &self.descriptor
+    }
+
+    /// Returns the normalized name of the chord without its root and slash-bass, so `Cm7/Bb` gives `min7`.
+    /// Unlike the descriptor, it is the same for every spelling of a chord, which makes it useful
+    /// to render the root apart (for example, for transposing instruments).
+    /// # Arguments
+    /// * `self` - The chord to get the descriptor from.
+    /// # Returns
+    /// * The normalized descriptor, which is empty for major triads.
+    pub fn normalized_descriptor(&self) -> String {
+        let mut descriptor = self.normalized[self.root.to_string().len()..].to_string();
+        if let Some(bass) = &self.bass {
+            let suffix = format!("/{}", bass);
+            if descriptor.ends_with(&suffix) {
+                descriptor.truncate(descriptor.len() - suffix.len());
+            }
+        }
+        descriptor
+    }
+
+    /// Checks if given note is part of the chord, either as a chord tone or as its bass.
+    /// Notes are compared by pitch class, so enharmonic spellings match (C# is found in a chord containing Db).
+    /// # Arguments
+    /// * `self` - The chord to look the note in.
+    /// * `note` - The note to look for.
+    /// # Returns
+    /// * True if the chord contains the note.
+    pub fn contains_note(&self, note: &Note) -> bool {
+        let st = note.to_semitone();
+        self.notes
+            .iter()
+            .chain(self.bass.as_ref())
+            .any(|n| n.to_semitone() == st)
+    }
+
+    /// Returns the notes of this chord also found in `other`, compared by pitch class.
+    /// Notes are spelled as in this chord, so the common tone of `E` and `Ab` is G# rather than Ab.
+    /// # Arguments
+    /// * `self` - The chord to take the notes from.
+    /// * `other` - The chord to compare with.
+    /// * `include_bass` - Whether slash-bass notes count, both in this chord and in `other`.
+    /// # Returns
+    /// * The common tones in the order of this chord, with the bass last if it is not a chord tone.
+    pub fn common_tones(&self, other: &Chord, include_bass: bool) -> Vec<Note> {
+        let bass = |chord: &'_ Chord| {
+            if include_bass {
+                chord.bass.clone()
+            } else {
+                None
+            }
+        };
+        let theirs = other
+            .notes
+            .iter()
+            .cloned()
+            .chain(bass(other))
+            .map(|n| n.to_semitone())
+            .collect::<Vec<u8>>();
+        let mut common: Vec<Note> = Vec::new();
+        for note in self.notes.iter().cloned().chain(bass(self)) {
+            let st = note.to_semitone();
+            if theirs.contains(&st) && !common.iter().any(|n| n.to_semitone() == st) {
+                common.push(note);
+            }
+        }
+        common
+    }
+
+    /// Returns the seventh of the chord, if any.
+    /// In the rare case of a chord with more than one seventh (like `Cdim7Maj7`), the minor seventh is preferred,
+    /// then the diminished one and then the major one.
+    /// # Arguments
+    /// * `self` - The chord to get the seventh from.
+    /// # Returns
+    /// * `MinorSeventh`, `MajorSeventh` or `DiminishedSeventh`, or None for triads and sixth chords.
+    pub fn seventh(&self) -> Option<Interval> {
+        [
+            Interval::MinorSeventh,
+            Interval::DiminishedSeventh,
+            Interval::MajorSeventh,
+        ]
+        .into_iter()
+        .find(|i| self.real_intervals.contains(i))
+    }
+
+    /// Checks whether the chord is a triad: a root, a fifth (of any kind) and a third or a sus second or fourth, with nothing else.
+    /// So `C`, `Cm`, `Cdim`, `C+`, `Csus4` and `C(b5)` are triads, but `C5`, `C6` and `C(omit5)` are not.
+    /// Note that `Csus2` is written as a chord with a ninth and no third (see [Parser::parse](crate::parsing::Parser::parse)), so it is extended rather than a triad.
+    /// The slash-bass is ignored.
+    /// # Arguments
+    /// * `self` - The chord to check.
+    /// # Returns
+    /// * True if the chord is a triad.
+    pub fn is_triad(&self) -> bool {
+        self.real_intervals.len() == 3
+            && self.has_sem(SemInterval::Fifth)
+            && (self.has_sem(SemInterval::Third)
+                || self.has_sem(SemInterval::Second)
+                || self.has_sem(SemInterval::Fourth))
+    }
+
+    /// Checks whether the chord is a seventh chord: a chord with a seventh (see [Chord::seventh]) and no extensions.
+    /// So `C7`, `CMaj7`, `Cm7b5`, `Cdim7`, `C7sus4` and `C7(omit5)` are seventh chords, while `C6` (a sixth is not a seventh)
+    /// and `C9` (which is extended) are not.
+    /// # Arguments
+    /// * `self` - The chord to check.
+    /// # Returns
+    /// * True if the chord is a seventh chord.
+    pub fn is_seventh_chord(&self) -> bool {
+        self.seventh().is_some() && !self.is_extended()
+    }
+
+    /// Checks whether the chord is extended: it has a ninth, an eleventh or a thirteenth, altered or not.
+    /// There is no need for a seventh, so `C6/9` and `C(add9)` are extended too.
+    /// A chord is at most one of a triad, a seventh chord or an extended chord, and chords like `C5` or `C6` are none of them.
+    /// # Arguments
+    /// * `self` - The chord to check.
+    /// # Returns
+    /// * True if the chord has any extension.
+    pub fn is_extended(&self) -> bool {
+        self.real_intervals.iter().any(|i| i.is_compound())
+    }
+
+    /// Returns the tensions of the chord: its ninths, elevenths and thirteenths, altered or not, in ascending order.
+    /// Everything else (the triad, the seventh, and sixths or sus fourths) is taken as a chord tone,
+    /// so plain triads, sixth chords and seventh chords have no tensions.
+    /// # Arguments
+    /// * `self` - The chord to get the tensions from.
+    /// # Returns
+    /// * The intervals of the tensions.
+    pub fn tensions(&self) -> Vec<Interval> {
+        self.real_intervals
+            .iter()
+            .filter(|i| i.is_compound())
+            .copied()
+            .collect()
+    }
+
+    /// Reads a dominant chord as a polychord: an upper structure triad over the dominant seventh chord,
+    /// like `D` over `C7` for `C7(9,#11,13)`.
+    /// A reading exists when a major or minor triad built on one of the tensions (see [Chord::tensions])
+    /// holds every tension of the chord, at least two of them, and nothing outside the chord.
+    /// The lower chord is the rest of the notes, keeping the slash-bass if any, so it lacks the fifth when a flat
+    /// thirteenth replaced it (`C7(#9,b13)` is `Ab` over `C7(omit5)`). Triads are compared by pitch class,
+    /// and when several fit the one on the lowest tension is taken.
+    /// # Arguments
+    /// * `self` - The chord to read.
+    /// # Returns
+    /// * The normalized names of the upper triad and the lower chord, or None if the chord is not a dominant chord
+    ///   or no triad gives a clean reading.
+    pub fn as_polychord(&self) -> Option<(String, String)> {
+        if self.quality != Quality::Dominant || !self.has(Interval::MinorSeventh) {
+            return None;
+        }
+        let pcs = self
+            .notes
+            .iter()
+            .map(|n| n.to_semitone())
+            .collect::<Vec<u8>>();
+        let tensions = self
+            .real_intervals
+            .iter()
+            .zip(&self.notes)
+            .filter(|(i, _)| i.is_compound())
+            .map(|(_, n)| n)
+            .collect::<Vec<&Note>>();
+        if tensions.len() < 2 {
+            return None;
+        }
+        let upper = tensions.iter().find_map(|root| {
+            [Interval::MajorThird, Interval::MinorThird]
+                .into_iter()
+                .map(|third| vec![Interval::Unison, third, Interval::PerfectFifth])
+                .find(|triad| {
+                    let triad_pcs = triad
+                        .iter()
+                        .map(|i| (root.to_semitone() + i.st()) % 12)
+                        .collect::<Vec<u8>>();
+                    triad_pcs.iter().all(|pc| pcs.contains(pc))
+                        && tensions
+                            .iter()
+                            .all(|t| triad_pcs.contains(&t.to_semitone()))
+                })
+                .map(|triad| Chord::from_root_and_intervals((*root).clone(), &triad))
+        })?;
+        let lower_intervals = self
+            .real_intervals
+            .iter()
+            .filter(|i| !i.is_compound())
+            .copied()
+            .collect::<Vec<Interval>>();
+        let mut lower = Chord::from_root_and_intervals(self.root.clone(), &lower_intervals);
+        if let Some(bass) = &self.bass {
+            lower = lower.with_bass(bass.clone());
+        }
+        Some((upper.normalized, lower.normalized))
+    }
+
+    /// Returns the simplest name of a slash chord, as the root-position chord its notes form over the bass.
+    /// The name changes only when the notes of the chord, bass included, are exactly one of the [presets] formulas
+    /// built over the bass, so `Am7/C` gives `C6` and `Dm7/G` gives `G9sus`.
+    /// Otherwise, for chords with no bass, inversions such as `C/E` and foreign basses such as `C/Bb`,
+    /// the chord is returned unchanged.
+    /// Candidates are ranked as in [presets::chord_names_containing], so the simplest formula wins.
+    /// # Arguments
+    /// * `self` - The chord to respell.
+    /// # Returns
+    /// * The root-position chord over the bass, or a copy of the chord if there is none.
+    pub fn simplest_spelling(&self) -> Chord {
+        let Some(bass) = &self.bass else {
+            return self.clone();
+        };
+        let mut notes = vec![bass.clone()];
+        notes.extend(self.notes.iter().cloned());
+        presets::chords_containing(&notes)
+            .into_iter()
+            .find(|(extra, chord)| *extra == 0 && chord.root.to_semitone() == bass.to_semitone())
+            .map(|(_, chord)| chord)
+            .unwrap_or_else(|| self.clone())
+    }
+
+    /// Returns the just intonation frequency ratio of every note of the chord relative to its root,
+    /// in the order of [Chord::real_intervals]. See [Interval::just_ratio] for the ratios used.
+    /// Multiplying the frequency of the root by each ratio tunes the chord in just intonation
+    /// instead of in equal temperament.
+    /// # Arguments
+    /// * `self` - The chord to get the ratios from.
+    /// # Returns
+    /// * The ratios, as numerators and denominators.
+    pub fn just_ratios(&self) -> Vec<(u32, u32)> {
+        self.real_intervals.iter().map(|i| i.just_ratio()).collect()
+    }
+
+    /// Returns the notes of the tensions of the chord (see [Chord::tensions]), spelled as in [Chord::notes].
+    /// These are the color tones of an arrangement, while the rest of the notes are chord tones.
+    /// # Arguments
+    /// * `self` - The chord to get the color tones from.
+    /// # Returns
+    /// * The notes of the tensions, in ascending order of their intervals.
+    pub fn color_tones(&self) -> Vec<Note> {
+        self.real_intervals
+            .iter()
+            .zip(&self.notes)
+            .filter(|(i, _)| i.is_compound())
+            .map(|(_, note)| note.clone())
+            .collect()
+    }
+
+    /// Returns the adds of the input string that had no effect, because their interval was already in the chord
+    /// (like the 3 in `Cmaj7add3` or the 9 in `C13add9`).
+    /// The chord is parsed anyway, this is meant to spot mistakes in the input.
+    /// # Arguments
+    /// * `self` - The chord to check.
+    /// # Returns
+    /// * The intervals of the redundant adds, in input order.
+    pub fn redundant_adds(&self) -> &[Interval] {
+        &self.redundant_adds
+    }
+
     /// Returns the MIDI codes for the chord, centered around central C (60 midi code).
+    /// The root is placed in the octave of the chord (see [Chord::octave]), an octave below central C by default.
+    /// Codes are in the order of the notes (the bass first if any, then the notes of [Chord::notes]).
+    /// Notes that would fall outside the MIDI range (0 to 127) are moved by octaves into it, so they keep their pitch class:
+    /// the upper extensions of chords with a root in the highest octaves are placed below the root instead,
+    /// and the codes are then no longer in ascending order.
     /// # Arguments
     /// * `self` - The chord to get the MIDI codes from.
     /// # Returns
     /// * A vector of MIDI codes.
     pub fn to_midi_codes(&self) -> Vec<u8> {
-        let root = self.root.to_midi_code();
+        let shift = 12 * (self.octave as i16 - DEFAULT_OCTAVE as i16);
+        let midi = |mut code: i16| {
+            while code > 127 {
+                code -= 12;
+            }
+            while code < 0 {
+                code += 12;
+            }
+            code as u8
+        };
+        let root = self.root.to_midi_code() as i16 + shift;
         let mut codes = vec![];
         if let Some(bass) = &self.bass {
-            codes.push(bass.to_midi_code() - 12);
-            codes.push(root);
+            codes.push(midi(bass.to_midi_code() as i16 + shift - 12));
+            codes.push(midi(root));
         } else {
-            codes.push(root - 12);
+            codes.push(midi(root - 12));
         }
         for note in self.real_intervals.iter().skip(1) {
-            codes.push(note.st() + root);
+            codes.push(midi(root + note.st() as i16));
+        }
+        codes
+    }
+
+    /// Returns the MIDI codes of [Chord::to_midi_codes] with the root an octave above the lowest root,
+    /// and optionally the fifth an octave above, for a fuller keyboard sound. So `C` gives `[36, 48, 52, 55]`,
+    /// and with the fifth `[36, 48, 52, 55, 67]`. The fifth is the one of the chord whatever its alteration,
+    /// and nothing is added for it if the chord has none.
+    /// A doubled note is left out when it would go beyond the MIDI range or is already one of the codes,
+    /// and the codes of [Chord::to_midi_codes] are kept as they are (see there how high chords are placed).
+    /// Doubling the fifth is optional, so unlike a plain `doubled_voicing(&self)` this takes whether to double it.
+    /// For more elaborate voicings see [generate_voicing](crate::voicings::generate_voicing).
+    /// # Arguments
+    /// * `self` - The chord to get the MIDI codes from.
+    /// * `double_fifth` - Whether to double the fifth as well as the root.
+    /// # Returns
+    /// * The MIDI codes in ascending order.
+    pub fn doubled_voicing(&self, double_fifth: bool) -> Vec<u8> {
+        let mut codes = self.to_midi_codes();
+        let root =
+            self.root.to_midi_code() as i16 + 12 * (self.octave as i16 - DEFAULT_OCTAVE as i16);
+        let mut doubled = vec![if self.bass.is_some() { root + 12 } else { root }];
+        if double_fifth {
+            let fifth = self
+                .real_intervals
+                .iter()
+                .find(|i| i.to_semantic_interval() == SemInterval::Fifth);
+            if let Some(fifth) = fifth {
+                doubled.push(root + fifth.st() as i16 + 12);
+            }
         }
+        for code in doubled {
+            if (0..=127).contains(&code) && !codes.contains(&(code as u8)) {
+                codes.push(code as u8);
+            }
+        }
+        codes.sort();
         codes
     }
 
+    /// Returns the notes of the chord sorted by pitch, using the same layout as [Chord::to_midi_codes].
+    /// The bass note comes first when present, below the root.
+    /// # Arguments
+    /// * `self` - The chord to get the notes from.
+    /// # Returns
+    /// * The notes of the chord in ascending pitch.
+    pub fn notes_ascending(&self) -> Vec<Note> {
+        let codes = self.to_midi_codes();
+        let mut notes = self
+            .bass
+            .iter()
+            .chain(&self.notes)
+            .zip(codes)
+            .collect::<Vec<_>>();
+        notes.sort_by_key(|(_, code)| *code);
+        notes.into_iter().map(|(note, _)| note.clone()).collect()
+    }
+
+    /// Returns the lowest sounding note of the chord, as placed by [Chord::to_midi_codes].
+    /// This is the slash-bass when present and the root otherwise, spelled as in [Chord::notes].
+    /// # Arguments
+    /// * `self` - The chord to get the note from.
+    /// # Returns
+    /// * The lowest note.
+    pub fn lowest_note(&self) -> Note {
+        self.bass.clone().unwrap_or_else(|| self.root.clone())
+    }
+
+    /// Returns the highest sounding note of the chord, as placed by [Chord::to_midi_codes].
+    /// # Arguments
+    /// * `self` - The chord to get the note from.
+    /// # Returns
+    /// * The highest note, spelled as in [Chord::notes].
+    pub fn highest_note(&self) -> Note {
+        self.notes_ascending()
+            .pop()
+            .expect("a chord always has at least its root")
+    }
+
+    /// Checks if two notes of [Chord::to_midi_codes] share a pitch class, like the bass and the third in `C/E`.
+    /// # Arguments
+    /// * `self` - The chord to check.
+    /// # Returns
+    /// * True if any pitch class is doubled.
+    pub fn has_doublings(&self) -> bool {
+        !self.doubled_degrees().is_empty()
+    }
+
+    /// Returns the intervals of the chord whose pitch class appears more than once in [Chord::to_midi_codes].
+    /// The slash-bass is not a degree by itself, so `C/E` gives the major third.
+    /// # Arguments
+    /// * `self` - The chord to check.
+    /// # Returns
+    /// * The doubled intervals, in the order of [Chord::real_intervals].
+    pub fn doubled_degrees(&self) -> Vec<Interval> {
+        let codes = self.to_midi_codes();
+        let offset = codes.len() - self.real_intervals.len();
+        self.real_intervals
+            .iter()
+            .zip(&codes[offset..])
+            .filter(|(_, code)| codes.iter().filter(|c| *c % 12 == *code % 12).count() > 1)
+            .map(|(interval, _)| *interval)
+            .collect()
+    }
+
+    /// Returns the MIDI code of the lowest note of the chord, as placed by [Chord::to_midi_codes].
+    /// This is the slash-bass when present and the root otherwise, both an octave below the root of the voicing.
+    /// # Arguments
+    /// * `self` - The chord to get the bass from.
+    /// # Returns
+    /// * The MIDI code of the bass note.
+    pub fn bass_midi_code(&self) -> Option<u8> {
+        self.to_midi_codes().first().copied()
+    }
+
+    /// Returns the interval from the slash-bass up to the root, so `C/E` gives a minor sixth and `C/G` a perfect fourth.
+    /// This is the inversion of the interval the bass forms above the root: a chord tone in the bass gives the
+    /// inversion of its own interval (the minor sixth of `C/E` inverts the major third), which tells inversions apart from foreign basses.
+    /// The interval is spelled from the letters of both notes (`C/F#` gives a diminished fifth but `C/Gb` an augmented fourth),
+    /// falling back to the most common interval with the same semitones when there is no such interval (like the augmented unison of `C#/C`).
+    /// # Arguments
+    /// * `self` - The chord to get the interval from.
+    /// # Returns
+    /// * The interval from the bass up to the root, or None if there is no slash-bass.
+    pub fn bass_interval(&self) -> Option<Interval> {
+        let bass = self.bass.as_ref()?;
+        let letters = (self.root.literal.numeric() + 7 - bass.literal.numeric()) % 7;
+        let st = (self.root.to_semitone() + 12 - bass.to_semitone()) % 12;
+        let interval = match (letters, st) {
+            (3, 6) => Interval::AugmentedFourth,
+            (4, 8) => Interval::AugmentedFifth,
+            (6, 9) => Interval::DiminishedSeventh,
+            (_, 0) => Interval::Unison,
+            (_, 1) => Interval::MinorSecond,
+            (_, 2) => Interval::MajorSecond,
+            (_, 3) => Interval::MinorThird,
+            (_, 4) => Interval::MajorThird,
+            (_, 5) => Interval::PerfectFourth,
+            (_, 6) => Interval::DiminishedFifth,
+            (_, 7) => Interval::PerfectFifth,
+            (_, 8) => Interval::MinorSixth,
+            (_, 9) => Interval::MajorSixth,
+            (_, 10) => Interval::MinorSeventh,
+            _ => Interval::MajorSeventh,
+        };
+        Some(interval)
+    }
+
+    /// Returns a table with a row for each note of the chord, showing its interval, its semitones from the root
+    /// and its degree. Columns are separated by `|` and aligned, with a header row on top:
+    /// ```text
+    /// note | interval | semitone | degree
+    /// -----+----------+----------+-------
+    /// C    | 1        | 0        | 1
+    /// E    | 3        | 4        | 3
+    /// G    | 5        | 7        | 5
+    /// ```
+    /// # Arguments
+    /// * `self` - The chord to dump.
+    /// # Returns
+    /// * The table, one line per row.
+    pub fn to_table(&self) -> String {
+        let mut rows = vec![["note", "interval", "semitone", "degree"].map(String::from)];
+        for (((note, interval), st), degree) in self
+            .notes
+            .iter()
+            .zip(&self.real_intervals)
+            .zip(&self.semitones)
+            .zip(&self.semantic_intervals)
+        {
+            rows.push([
+                note.to_string(),
+                interval.to_string(),
+                st.to_string(),
+                degree.to_string(),
+            ]);
+        }
+        let mut widths = [0; 4];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let line = |row: &[String; 4]| {
+            row.iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell))
+                .collect::<Vec<String>>()
+                .join(" | ")
+                .trim_end()
+                .to_string()
+        };
+        let separator = widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<String>>()
+            .join("-+-");
+        let mut table = vec![line(&rows[0]), separator];
+        table.extend(rows[1..].iter().map(line));
+        table.join("\n")
+    }
+
+    /// Returns the distance in semitones from the lowest to the highest note of the chord in close position.
+    /// This does not follow the layout of [Chord::to_midi_codes], which places the root or the bass an octave lower
+    /// (so that `C` spans 19 semitones there). Here the chord tones are placed above the root, and the lowest note is
+    /// the root itself when there is no slash-bass, or the closest slash-bass note strictly below the root otherwise.
+    /// So `C` spans 7 semitones, `C9` spans 14, `C/E` spans 15 (E up to G, crossing the root)
+    /// and `C/C` spans 19, since its bass is an octave below the root.
+    /// # Arguments
+    /// * `self` - The chord to measure.
+    /// # Returns
+    /// * The span of the chord in semitones.
+    pub fn span_semitones(&self) -> u8 {
+        let top = self
+            .real_intervals
+            .iter()
+            .map(|i| i.st())
+            .max()
+            .unwrap_or(0);
+        let below = self
+            .bass
+            .as_ref()
+            .map(|bass| (self.root.to_semitone() + 11 - bass.to_semitone()) % 12 + 1)
+            .unwrap_or(0);
+        top + below
+    }
+
+    /// Checks if the chord spans an octave or less in close position, as measured by [Chord::span_semitones].
+    /// # Arguments
+    /// * `self` - The chord to check.
+    /// # Returns
+    /// * True if [Chord::span_semitones] is 12 or less.
+    pub fn fits_in_octave(&self) -> bool {
+        self.span_semitones() <= 12
+    }
+
     /// Returns the JSON representation of the chord.
     /// # Arguments
     /// * `self` - The chord to get the JSON representation from.
@@ -137,6 +1400,126 @@ impl Chord {
         }
     }
 
+    /// Returns the intervals of the chord as an [IntervalSet], for fast set operations between chords.
+    /// It has the same intervals as `real_intervals`, the root included.
+    /// # Arguments
+    /// * `self` - The chord to get the intervals from.
+    /// # Returns
+    /// * The set of intervals of the chord.
+    pub fn interval_set(&self) -> IntervalSet {
+        self.real_intervals.iter().copied().collect()
+    }
+
+    /// Returns what changes from this chord to another one, like the intervals to add and remove.
+    /// Intervals are compared relative to each root through [Chord::interval_set], so `C7` to `D7` only changes the root,
+    /// and they are listed in ascending order. Chords with the same notes can still differ: `C7` to `C7(#9)`
+    /// adds a sharp ninth even though it sounds as a minor third.
+    /// # Arguments
+    /// * `self` - The chord to start from.
+    /// * `other` - The chord to go to.
+    /// # Returns
+    /// * The differences between the chords, see [ChordDiff].
+    pub fn diff(&self, other: &Chord) -> ChordDiff {
+        let (from, to) = (self.interval_set(), other.interval_set());
+        ChordDiff {
+            added: to.difference(&from).iter().collect(),
+            removed: from.difference(&to).iter().collect(),
+            root_changed: self.root != other.root,
+            bass_changed: self.bass != other.bass,
+            quality_changed: self.quality != other.quality,
+        }
+    }
+
+    /// Returns a stable number identifying the shape of the chord, meant as a key for indexing chords.
+    /// It packs, from the lowest bit up:
+    /// - bits 0 to 22: the bits of the [IntervalSet] of the chord.
+    /// - bits 23 to 26: the pitch class of the root, from 0 (C) to 11 (B).
+    /// - bits 27 to 30: 0 if there is no slash-bass, or the pitch class of the bass plus one.
+    ///
+    /// Nothing else is included, so the origin, the spelling of the notes and the octave do not change it:
+    /// `C#7/G#` and `Db7/Ab` share a fingerprint, while `C6` and `Amin7/C` do not, since their roots differ.
+    /// # Arguments
+    /// * `self` - The chord to fingerprint.
+    /// # Returns
+    /// * The fingerprint of the chord.
+    pub fn fingerprint(&self) -> u32 {
+        let root = self.root.to_semitone() as u32;
+        let bass = self
+            .bass
+            .as_ref()
+            .map_or(0, |bass| bass.to_semitone() as u32 + 1);
+        self.interval_set().bits() | root << 23 | bass << 27
+    }
+
+    /// Returns the pitch classes of the chord as a 12-bit mask, where bit 0 is C, bit 1 is C#/Db and so on up to bit 11 for B.
+    /// Chords with the same notes have the same mask whatever their name or spelling, so `C6` and `Amin7/C` share it
+    /// when the bass is not included, and `C7` and `C7(b5,#5)` do not.
+    /// # Arguments
+    /// * `self` - The chord to get the mask from.
+    /// * `include_bass` - Whether the slash-bass note, if any, is part of the mask.
+    /// # Returns
+    /// * The mask of the pitch classes of the chord.
+    pub fn pitch_mask(&self, include_bass: bool) -> u16 {
+        let bass = self.bass.iter().filter(|_| include_bass);
+        self.notes
+            .iter()
+            .chain(bass)
+            .fold(0, |mask, note| mask | 1 << note.to_semitone())
+    }
+
+    /// Returns a compact binary representation of the chord, meant for storing many chords.
+    /// It takes 7 bytes: the root, the bass (`0xFF` if there is none), the bits of the [IntervalSet] of the chord
+    /// in little-endian order, and the quality.
+    /// Only the notes are kept, so the input spelling is lost and [Chord::from_bytes] gives back the normalized chord.
+    /// # Arguments
+    /// * `self` - The chord to encode.
+    /// # Returns
+    /// * The encoded chord.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let quality = Quality::all()
+            .iter()
+            .position(|q| *q == self.quality)
+            .unwrap_or_default() as u8;
+        let mut bytes = vec![
+            self.root.to_byte(),
+            self.bass.as_ref().map_or(0xFF, |b| b.to_byte()),
+        ];
+        bytes.extend(self.interval_set().bits().to_le_bytes());
+        bytes.push(quality);
+        bytes
+    }
+
+    /// Decodes a chord encoded by [Chord::to_bytes].
+    /// The chord is rebuilt from its notes as in [Chord::from_root_and_intervals], so its origin is the normalized name.
+    /// # Arguments
+    /// * `bytes` - The encoded chord.
+    /// # Returns
+    /// * The decoded chord, or None if the bytes are not a valid encoding (wrong length, unknown notes or intervals,
+    ///   or a quality that does not match the intervals).
+    pub fn from_bytes(bytes: &[u8]) -> Option<Chord> {
+        let [root, bass, i0, i1, i2, i3, quality] = bytes else {
+            return None;
+        };
+        let root = Note::from_byte(*root)?;
+        let bass = match bass {
+            0xFF => None,
+            b => Some(Note::from_byte(*b)?),
+        };
+        let intervals = IntervalSet::from_bits(u32::from_le_bytes([*i0, *i1, *i2, *i3]))?
+            .iter()
+            .collect::<Vec<Interval>>();
+        let mut chord = Chord::from_root_and_intervals(root, &intervals);
+        if Quality::all().get(*quality as usize) != Some(&chord.quality) {
+            return None;
+        }
+        if let Some(bass) = bass {
+            chord = chord.with_bass(bass);
+            chord.origin = chord.normalized.clone();
+            chord.descriptor = chord.normalized_descriptor();
+        }
+        Some(chord)
+    }
+
     pub(crate) fn has(&self, int: Interval) -> bool {
         self.rbs[int.st() as usize]
     }
@@ -161,6 +1544,8 @@ pub struct ChordBuilder {
     is_sus: bool,
     adds: Vec<Interval>,
     rbs: [bool; 24],
+    redundant_adds: Vec<Interval>,
+    octave: u8,
 }
 
 impl ChordBuilder {
@@ -179,6 +1564,8 @@ impl ChordBuilder {
             is_sus: false,
             adds: Vec::new(),
             rbs: [false; 24],
+            redundant_adds: Vec::new(),
+            octave: DEFAULT_OCTAVE,
         }
     }
 
@@ -232,6 +1619,16 @@ impl ChordBuilder {
         self
     }
 
+    pub fn redundant_adds(mut self, redundant_adds: Vec<Interval>) -> ChordBuilder {
+        self.redundant_adds = redundant_adds;
+        self
+    }
+
+    pub fn octave(mut self, octave: u8) -> ChordBuilder {
+        self.octave = octave.clamp(MIN_OCTAVE, MAX_OCTAVE);
+        self
+    }
+
     pub fn normalized(mut self, normalized: String) -> ChordBuilder {
         self.normalized = normalized;
         self
@@ -254,6 +1651,8 @@ impl ChordBuilder {
             semitones: self.semitones,
             adds: self.adds,
             rbs: self.rbs,
+            redundant_adds: self.redundant_adds,
+            octave: self.octave,
         };
         chord.complete_quality = InnerQuality::from_chord(&chord);
         chord.quality = Quality::quality(&chord.rbs);
@@ -1,10 +1,145 @@
+//! Normalization of chord names
+use crate::parsing::{lexer::Lexer, token::TokenType};
+
 use super::{
     intervals::{Interval, SemInterval},
     quality::InnerQuality,
     Chord,
 };
 
-pub fn normalize(ch: &Chord) -> String {
+/// The way quality symbols are written when naming a chord.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NamingStyle {
+    /// Always writes `Maj`, `min` and `dim`, as in the `normalized` field of a chord.
+    #[default]
+    Normalized,
+    /// Writes the major, minor and diminished symbols found in the chord origin (like `△`, `-`, `ma` or `°`),
+    /// falling back to the normalized ones for symbols not present there.
+    /// Everything else, like the order of extensions and alterations, is still normalized.
+    PreserveInput,
+    /// Writes the normalized keywords (`min`, `Maj`, `dim`, `sus`, `add` and `omit`) with the casing used for them
+    /// in the chord origin, so `CMIN7(ADD11)` gives `CMIN7(ADD11)` and `Cmaj7` gives `Cmaj7`.
+    /// Only keywords written all uppercase, all lowercase or capitalized are matched,
+    /// and single letters like `m` or `M` are left as normalized, since their case tells minor from major.
+    MatchCase,
+    /// Drops the parentheses around a single alteration, as many fake books print chords, so `C7(b9)` gives `C7b9`.
+    /// Parentheses are kept when dropping them could change how the name is read:
+    /// - when the group holds more than one item, like `C7(b9,#9)`;
+    /// - when the item is not an alteration (`add`, `omit`), so its digits could run into the preceding ones;
+    /// - when the group does not follow a digit, since the accidental could then be read as part of the root
+    ///   (`C(b5)` is not `Cb5`) or as the target of a keyword (`C7sus(b9)` is not `C7susb9`).
+    Compact,
+}
+
+/// Returns the normalized name of the chord written in the given style.
+pub(crate) fn normalize_with(ch: &Chord, style: NamingStyle) -> String {
+    let normalized = normalize(ch);
+    let replacements = match style {
+        NamingStyle::Normalized => return normalized,
+        NamingStyle::PreserveInput => input_symbols(&ch.origin),
+        NamingStyle::MatchCase => input_cases(&ch.origin),
+        NamingStyle::Compact => return compact(&normalized),
+    };
+    let root_len = ch.root.to_string().len();
+    let mut descriptor = normalized[root_len..].to_string();
+    for (normalized_symbol, symbol) in replacements {
+        descriptor = descriptor.replace(normalized_symbol, &symbol);
+    }
+    format!("{}{}", &normalized[..root_len], descriptor)
+}
+
+/// Drops the parentheses of `normalized` around single alterations following a digit, see [NamingStyle::Compact].
+fn compact(normalized: &str) -> String {
+    let mut res = String::new();
+    let mut rest = normalized;
+    while let Some(open) = rest.find('(') {
+        res.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find(')').map(|close| open + close) else {
+            break;
+        };
+        let group = &rest[open + 1..close];
+        let single_alteration = !group.contains(',') && group.starts_with(['b', '#']);
+        if single_alteration && res.ends_with(|c: char| c.is_ascii_digit()) {
+            res.push_str(group);
+        } else {
+            res.push_str(&rest[open..=close]);
+        }
+        rest = &rest[close + 1..];
+    }
+    res.push_str(rest);
+    res
+}
+
+/// Scans `origin`, returning every token along with the literal it was read from.
+fn input_literals(origin: &str) -> Vec<(TokenType, String)> {
+    Lexer::new()
+        .scan_tokens(origin)
+        .into_iter()
+        .map(|t| {
+            let literal = origin.chars().skip(t.pos - 1).take(t.len).collect();
+            (t.token_type, literal)
+        })
+        .collect()
+}
+
+/// Finds the symbols used in `origin` for minor, major and diminished qualities,
+/// paired with the normalized symbol they stand for.
+fn input_symbols(origin: &str) -> Vec<(&'static str, String)> {
+    let literals = input_literals(origin);
+    let literal = |token_type: &dyn Fn(&TokenType) -> bool| {
+        literals
+            .iter()
+            .find(|(t, _)| token_type(t))
+            .map(|(_, literal)| literal.clone())
+    };
+    let mut symbols = Vec::new();
+    if let Some(minor) = literal(&|t| matches!(t, TokenType::Minor | TokenType::Hyphen)) {
+        symbols.push(("min", minor));
+    }
+    if let Some(major) = literal(&|t| matches!(t, TokenType::Maj | TokenType::Maj7)) {
+        symbols.push(("Maj", major));
+    }
+    if let Some(dim) = literal(&|t| matches!(t, TokenType::Dim)) {
+        symbols.push(("dim", dim));
+    }
+    symbols
+}
+
+/// Finds the casing used in `origin` for every normalized keyword,
+/// paired with the normalized keyword written in that casing.
+fn input_cases(origin: &str) -> Vec<(&'static str, String)> {
+    let keywords = [
+        ("min", TokenType::Minor),
+        ("Maj", TokenType::Maj),
+        ("dim", TokenType::Dim),
+        ("sus", TokenType::Sus),
+        ("add", TokenType::Add),
+        ("omit", TokenType::Omit),
+    ];
+    let literals = input_literals(origin);
+    keywords
+        .into_iter()
+        .filter_map(|(keyword, token_type)| {
+            let (_, literal) = literals.iter().find(|(t, _)| *t == token_type)?;
+            if literal.len() < 2 || !literal.chars().all(|c| c.is_ascii_alphabetic()) {
+                return None;
+            }
+            let mut rest = literal.chars().skip(1);
+            let cased = if literal.chars().all(|c| c.is_ascii_uppercase()) {
+                keyword.to_uppercase()
+            } else if literal.chars().all(|c| c.is_ascii_lowercase()) {
+                keyword.to_lowercase()
+            } else if rest.all(|c| c.is_ascii_lowercase()) {
+                format!("{}{}", keyword[..1].to_uppercase(), &keyword[1..])
+            } else {
+                return None;
+            };
+            Some((keyword, cased))
+        })
+        .collect()
+}
+
+pub(crate) fn normalize(ch: &Chord) -> String {
     let mut res = ch.root.to_string();
     if ch.real_intervals.len() == 1 {
         res.push_str("Bass");
@@ -14,6 +149,10 @@ pub fn normalize(ch: &Chord) -> String {
     match ch.complete_quality {
         InnerQuality::Power => {
             res.push('5');
+            if let Some(bass) = &ch.bass {
+                res.push('/');
+                res.push_str(&bass.to_string());
+            }
             res
         }
         InnerQuality::Major6 => {
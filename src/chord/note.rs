@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 /// All possible note literals.
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum NoteLiteral {
     C = 0,
@@ -35,6 +35,24 @@ impl NoteLiteral {
         *self as u8
     }
 
+    /// Returns the note literal `by` letters away from this one, wrapping around the octave in both directions.
+    /// For example, B stepped by 1 is C, and C stepped by -1 is B.
+    /// # Arguments
+    /// * `by` - The number of letters to move, upwards if positive and downwards if negative
+    /// # Returns
+    /// The resulting note literal
+    pub fn step(&self, by: i8) -> NoteLiteral {
+        match (self.numeric() as i16 + by as i16).rem_euclid(7) {
+            0 => NoteLiteral::C,
+            1 => NoteLiteral::D,
+            2 => NoteLiteral::E,
+            3 => NoteLiteral::F,
+            4 => NoteLiteral::G,
+            5 => NoteLiteral::A,
+            _ => NoteLiteral::B,
+        }
+    }
+
     /// Returns the matcher for given root and interval.  
     /// It is used to find the enharmonically correct note given an interval and a root
     /// # Arguments
@@ -131,7 +149,7 @@ impl Display for NoteLiteral {
 pub type NoteMatcher = Vec<(NoteLiteral, Option<Modifier>)>;
 
 /// Represents a note modifier. It can be sharp, flat, double sharp or double flat.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum Modifier {
     Sharp,
@@ -151,8 +169,21 @@ impl Display for Modifier {
     }
 }
 
+/// The function of a note over a chord, see [Chord::classify_note](super::Chord::classify_note).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum NoteRole {
+    /// A note of the chord up to the seventh, or its slash-bass.
+    ChordTone,
+    /// An extension of the chord, or a note of its scale that can be added as one.
+    Tension,
+    /// A note of the scale of the chord that clashes with it.
+    Avoid,
+    /// A note outside the scale of the chord.
+    Outside,
+}
+
 /// Represents a musical note, splited into its literal and its modifier if any.
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct Note {
     pub literal: NoteLiteral,
     pub modifier: Option<Modifier>,
@@ -183,68 +214,74 @@ impl Note {
         Note::new(m[0].clone().0, m[0].clone().1)
     }
 
+    /// Returns the other common spelling of the note, written with a single accidental at most.
+    /// So C# gives Db, B# gives C and E gives Fb, while notes like D have no such spelling (only E𝄫 and C𝄪).
+    /// Notes with double accidentals give their natural or single accidental spelling, so E𝄫 gives D.
+    /// # Returns
+    /// The alternate spelling, or None if there is none with a single accidental at most
+    pub fn enharmonic(&self) -> Option<Note> {
+        self.literal
+            .get_matcher(self.to_semitone(), 0)
+            .into_iter()
+            .filter(|(literal, modifier)| {
+                *literal != self.literal
+                    && !matches!(modifier, Some(Modifier::DSharp) | Some(Modifier::DFlat))
+            })
+            .min_by_key(|(_, modifier)| modifier.is_some())
+            .map(|(literal, modifier)| Note::new(literal, modifier))
+    }
+
+    /// Packs the note into a single byte, the literal in the low nibble and the modifier in the high one.
+    pub(crate) fn to_byte(&self) -> u8 {
+        let modifier = match &self.modifier {
+            None => 0,
+            Some(Modifier::Sharp) => 1,
+            Some(Modifier::Flat) => 2,
+            Some(Modifier::DSharp) => 3,
+            Some(Modifier::DFlat) => 4,
+        };
+        modifier << 4 | self.literal.numeric()
+    }
+
+    /// Unpacks a note packed by [Note::to_byte], or returns None if the byte is not a valid note.
+    pub(crate) fn from_byte(byte: u8) -> Option<Note> {
+        let literal = byte & 0x0F;
+        if literal > 6 {
+            return None;
+        }
+        let modifier = match byte >> 4 {
+            0 => None,
+            1 => Some(Modifier::Sharp),
+            2 => Some(Modifier::Flat),
+            3 => Some(Modifier::DSharp),
+            4 => Some(Modifier::DFlat),
+            _ => return None,
+        };
+        Some(Note::new(NoteLiteral::C.step(literal as i8), modifier))
+    }
+
     /// Returns the semitone distance taking C as reference.
+    /// Double sharps and double flats are allowed, so Bbb gives 9 and B## gives 1.
     /// # Returns
     /// The semitone distance from C
     pub fn to_semitone(&self) -> u8 {
-        match self.literal {
-            NoteLiteral::C => match &self.modifier {
-                Some(m) => match m {
-                    Modifier::Sharp => 1,
-                    Modifier::Flat => 11,
-                    _ => panic!("Double flat/sharp roots are not allowed"),
-                },
-                None => 0,
-            },
-            NoteLiteral::D => match &self.modifier {
-                Some(m) => match m {
-                    Modifier::Sharp => 3,
-                    Modifier::Flat => 1,
-                    _ => panic!("Double flat/sharp roots are not allowed"),
-                },
-                None => 2,
-            },
-            NoteLiteral::E => match &self.modifier {
-                Some(m) => match m {
-                    Modifier::Sharp => 5,
-                    Modifier::Flat => 3,
-                    _ => panic!("Double flat/sharp roots are not allowed"),
-                },
-                None => 4,
-            },
-            NoteLiteral::F => match &self.modifier {
-                Some(m) => match m {
-                    Modifier::Sharp => 6,
-                    Modifier::Flat => 4,
-                    _ => panic!("Double flat/sharp roots are not allowed"),
-                },
-                None => 5,
-            },
-            NoteLiteral::G => match &self.modifier {
-                Some(m) => match m {
-                    Modifier::Sharp => 8,
-                    Modifier::Flat => 6,
-                    _ => panic!("Double flat/sharp roots are not allowed"),
-                },
-                None => 7,
-            },
-            NoteLiteral::A => match &self.modifier {
-                Some(m) => match m {
-                    Modifier::Sharp => 10,
-                    Modifier::Flat => 8,
-                    _ => panic!("Double flat/sharp roots are not allowed"),
-                },
-                None => 9,
-            },
-            NoteLiteral::B => match &self.modifier {
-                Some(m) => match m {
-                    Modifier::Sharp => 0,
-                    Modifier::Flat => 10,
-                    _ => panic!("Double flat/sharp roots are not allowed"),
-                },
-                None => 11,
-            },
-        }
+        let natural: u8 = match self.literal {
+            NoteLiteral::C => 0,
+            NoteLiteral::D => 2,
+            NoteLiteral::E => 4,
+            NoteLiteral::F => 5,
+            NoteLiteral::G => 7,
+            NoteLiteral::A => 9,
+            NoteLiteral::B => 11,
+        };
+        let modifier = match &self.modifier {
+            Some(Modifier::Sharp) => 1,
+            Some(Modifier::Flat) => 11,
+            Some(Modifier::DSharp) => 2,
+            Some(Modifier::DFlat) => 10,
+            None => 0,
+        };
+        (natural + modifier) % 12
     }
 
     /// Given a semitone distance from root and a semantic interval, returns the enharmonically correct note.
@@ -255,9 +292,8 @@ impl Note {
     /// The enharmonically correct note relative to root
     pub fn get_note(&self, semitone: u8, semantic_interval: u8) -> Note {
         let m = self.literal.get_matcher(self.to_semitone(), semitone);
-        let root_index = &self.literal.numeric();
-        let interval_index = (root_index + (semantic_interval - 1)) % 7;
-        let f = m.iter().find(|m| m.0.numeric() == interval_index);
+        let literal = self.literal.step(semantic_interval as i8 - 1);
+        let f = m.iter().find(|m| m.0 == literal);
 
         // If the note is triple flat/sharp return the first note, it is wrong anyway
         // Maybe in the future we can try to get the most reasonable note and return some kind of warning attached to the chord
@@ -265,6 +301,25 @@ impl Note {
         Note::new(literal, modifier)
     }
 
+    /// Returns the degree of the note in the major scale of `key`, along with its alteration.
+    /// The degree comes from the letter distance, so C# in C is an altered first degree while Db is an altered second degree.
+    /// # Arguments
+    /// * `key` - The tonic of the major scale
+    /// # Returns
+    /// The 1-based degree and the semitones it is raised (positive) or lowered (negative) from the scale note
+    pub(crate) fn scale_degree(&self, key: &Note) -> (u8, i8) {
+        let major = [0, 2, 4, 5, 7, 9, 11];
+        let degree = (self.literal.numeric() + 7 - key.literal.numeric()) % 7;
+        let actual = (self.to_semitone() + 12 - key.to_semitone()) % 12;
+        let mut alteration = actual as i8 - major[degree as usize];
+        if alteration > 6 {
+            alteration -= 12;
+        } else if alteration < -6 {
+            alteration += 12;
+        }
+        (degree + 1, alteration)
+    }
+
     /// Returns the MIDI code of the note.
     /// # Returns
     /// The MIDI code of the note centered around central C
@@ -362,4 +417,80 @@ mod test {
             assert_eq!(expect, note.get_note(dist, sem_interval.numeric()))
         }
     }
+
+    #[test]
+    fn semitones() {
+        let cases = vec![
+            (Note::new(NoteLiteral::C, None), 0),
+            (Note::new(NoteLiteral::C, Some(Modifier::Flat)), 11),
+            (Note::new(NoteLiteral::B, Some(Modifier::Sharp)), 0),
+            (Note::new(NoteLiteral::E, Some(Modifier::DFlat)), 2),
+            (Note::new(NoteLiteral::C, Some(Modifier::DFlat)), 10),
+            (Note::new(NoteLiteral::B, Some(Modifier::DSharp)), 1),
+            (Note::new(NoteLiteral::F, Some(Modifier::DSharp)), 7),
+        ];
+        for (note, expect) in cases {
+            assert_eq!(expect, note.to_semitone())
+        }
+    }
+
+    #[test]
+    fn enharmonics() {
+        let cases = vec![
+            (
+                Note::new(NoteLiteral::C, Some(Modifier::Sharp)),
+                Some(Note::new(NoteLiteral::D, Some(Modifier::Flat))),
+            ),
+            (
+                Note::new(NoteLiteral::D, Some(Modifier::Flat)),
+                Some(Note::new(NoteLiteral::C, Some(Modifier::Sharp))),
+            ),
+            (
+                Note::new(NoteLiteral::B, Some(Modifier::Sharp)),
+                Some(Note::new(NoteLiteral::C, None)),
+            ),
+            (
+                Note::new(NoteLiteral::C, None),
+                Some(Note::new(NoteLiteral::B, Some(Modifier::Sharp))),
+            ),
+            (
+                Note::new(NoteLiteral::F, Some(Modifier::Flat)),
+                Some(Note::new(NoteLiteral::E, None)),
+            ),
+            (
+                Note::new(NoteLiteral::E, Some(Modifier::DFlat)),
+                Some(Note::new(NoteLiteral::D, None)),
+            ),
+            (
+                Note::new(NoteLiteral::F, Some(Modifier::DSharp)),
+                Some(Note::new(NoteLiteral::G, None)),
+            ),
+            (Note::new(NoteLiteral::D, None), None),
+            (Note::new(NoteLiteral::G, None), None),
+            (Note::new(NoteLiteral::A, None), None),
+        ];
+        for (note, expect) in cases {
+            assert_eq!(expect, note.enharmonic())
+        }
+    }
+
+    #[test]
+    fn steps() {
+        let cases = vec![
+            (NoteLiteral::C, 0, NoteLiteral::C),
+            (NoteLiteral::C, 2, NoteLiteral::E),
+            (NoteLiteral::A, 1, NoteLiteral::B),
+            (NoteLiteral::B, 1, NoteLiteral::C),
+            (NoteLiteral::G, 5, NoteLiteral::E),
+            (NoteLiteral::C, -1, NoteLiteral::B),
+            (NoteLiteral::D, -3, NoteLiteral::A),
+            (NoteLiteral::E, 7, NoteLiteral::E),
+            (NoteLiteral::F, -14, NoteLiteral::F),
+            (NoteLiteral::B, 12, NoteLiteral::G),
+            (NoteLiteral::C, i8::MIN, NoteLiteral::A),
+        ];
+        for (literal, by, expect) in cases {
+            assert_eq!(expect, literal.step(by))
+        }
+    }
 }
@@ -0,0 +1,159 @@
+//! Interval formulas of common chords
+//!
+//! Every formula starts with the root and lists its intervals in ascending order.
+//! They can be turned into chords with [Chord::from_root_and_intervals](super::Chord::from_root_and_intervals).
+use super::{
+    intervals::Interval::{self, *},
+    note::{Modifier, Note, NoteLiteral},
+    Chord,
+};
+
+pub const POWER: &[Interval] = &[Unison, PerfectFifth];
+pub const MAJOR_TRIAD: &[Interval] = &[Unison, MajorThird, PerfectFifth];
+pub const MINOR_TRIAD: &[Interval] = &[Unison, MinorThird, PerfectFifth];
+pub const DIMINISHED_TRIAD: &[Interval] = &[Unison, MinorThird, DiminishedFifth];
+pub const AUGMENTED_TRIAD: &[Interval] = &[Unison, MajorThird, AugmentedFifth];
+/// The second of sus2 chords is taken as a ninth, as the parser does for `Csus2`.
+pub const SUS2: &[Interval] = &[Unison, PerfectFifth, Ninth];
+pub const SUS4: &[Interval] = &[Unison, PerfectFourth, PerfectFifth];
+pub const MAJOR_6: &[Interval] = &[Unison, MajorThird, PerfectFifth, MajorSixth];
+pub const MINOR_6: &[Interval] = &[Unison, MinorThird, PerfectFifth, MajorSixth];
+pub const SIX_NINE: &[Interval] = &[Unison, MajorThird, PerfectFifth, MajorSixth, Ninth];
+pub const ADD_9: &[Interval] = &[Unison, MajorThird, PerfectFifth, Ninth];
+pub const MAJOR_7: &[Interval] = &[Unison, MajorThird, PerfectFifth, MajorSeventh];
+pub const DOMINANT_7: &[Interval] = &[Unison, MajorThird, PerfectFifth, MinorSeventh];
+pub const DOMINANT_7_SUS4: &[Interval] = &[Unison, PerfectFourth, PerfectFifth, MinorSeventh];
+pub const MINOR_7: &[Interval] = &[Unison, MinorThird, PerfectFifth, MinorSeventh];
+pub const MINOR_MAJOR_7: &[Interval] = &[Unison, MinorThird, PerfectFifth, MajorSeventh];
+pub const MINOR_7B5: &[Interval] = &[Unison, MinorThird, DiminishedFifth, MinorSeventh];
+pub const DIMINISHED_7: &[Interval] = &[Unison, MinorThird, DiminishedFifth, DiminishedSeventh];
+pub const AUGMENTED_7: &[Interval] = &[Unison, MajorThird, AugmentedFifth, MinorSeventh];
+pub const MAJOR_9: &[Interval] = &[Unison, MajorThird, PerfectFifth, MajorSeventh, Ninth];
+pub const DOMINANT_9: &[Interval] = &[Unison, MajorThird, PerfectFifth, MinorSeventh, Ninth];
+pub const MINOR_9: &[Interval] = &[Unison, MinorThird, PerfectFifth, MinorSeventh, Ninth];
+pub const DOMINANT_11: &[Interval] = &[Unison, PerfectFifth, MinorSeventh, Ninth, Eleventh];
+pub const MINOR_11: &[Interval] = &[
+    Unison,
+    MinorThird,
+    PerfectFifth,
+    MinorSeventh,
+    Ninth,
+    Eleventh,
+];
+pub const MAJOR_13: &[Interval] = &[
+    Unison,
+    MajorThird,
+    PerfectFifth,
+    MajorSeventh,
+    Ninth,
+    Thirteenth,
+];
+pub const DOMINANT_13: &[Interval] = &[
+    Unison,
+    MajorThird,
+    PerfectFifth,
+    MinorSeventh,
+    Ninth,
+    Thirteenth,
+];
+
+/// Every formula of this module, from the simplest to the richest chords as they are declared.
+pub const ALL: &[&[Interval]] = &[
+    POWER,
+    MAJOR_TRIAD,
+    MINOR_TRIAD,
+    DIMINISHED_TRIAD,
+    AUGMENTED_TRIAD,
+    SUS2,
+    SUS4,
+    MAJOR_6,
+    MINOR_6,
+    SIX_NINE,
+    ADD_9,
+    MAJOR_7,
+    DOMINANT_7,
+    DOMINANT_7_SUS4,
+    MINOR_7,
+    MINOR_MAJOR_7,
+    MINOR_7B5,
+    DIMINISHED_7,
+    AUGMENTED_7,
+    MAJOR_9,
+    DOMINANT_9,
+    MINOR_9,
+    DOMINANT_11,
+    MINOR_11,
+    MAJOR_13,
+    DOMINANT_13,
+];
+
+/// Finds the chords of [ALL] formulas, over any of the 12 roots, that have every given note, compared by pitch class.
+/// This is meant to name partial voicings, where the played notes are only part of the chord.
+///
+/// Chords are ranked by the number of notes they add to the given ones, so `C, E, G` gives `C` first, then `C6`, `C(add9)` and `CMaj7`, each adding one note.
+/// Ties go first to chords whose root is one of the given notes, and then to the simplest formula.
+/// Roots are spelled as in the given notes when possible, and otherwise with flats (F# being the only sharp).
+/// # Arguments
+/// * `notes` - The notes the chords must contain.
+/// # Returns
+/// The normalized names of the matching chords, best first, or an empty list if there are no notes.
+pub fn chord_names_containing(notes: &[Note]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for (_, chord) in chords_containing(notes) {
+        if !names.contains(&chord.normalized) {
+            names.push(chord.normalized);
+        }
+    }
+    names
+}
+
+/// Same as [chord_names_containing], but returns the chords along with the number of notes each adds to the given ones.
+pub(crate) fn chords_containing(notes: &[Note]) -> Vec<(usize, Chord)> {
+    if notes.is_empty() {
+        return Vec::new();
+    }
+    let pcs = notes.iter().map(|n| n.to_semitone()).collect::<Vec<u8>>();
+    let mut found = Vec::new();
+    for (index, formula) in ALL.iter().enumerate() {
+        for pc in 0..12 {
+            let root = notes
+                .iter()
+                .find(|n| n.to_semitone() == pc)
+                .cloned()
+                .unwrap_or_else(|| default_spelling(pc));
+            let chord = Chord::from_root_and_intervals(root, formula);
+            let chord_pcs = chord
+                .notes
+                .iter()
+                .map(|n| n.to_semitone())
+                .collect::<Vec<u8>>();
+            if pcs.iter().all(|pc| chord_pcs.contains(pc)) {
+                let extra = chord_pcs.iter().filter(|pc| !pcs.contains(pc)).count();
+                found.push(((extra, !pcs.contains(&pc), index), chord));
+            }
+        }
+    }
+    found.sort_by_key(|(rank, _)| *rank);
+    found
+        .into_iter()
+        .map(|((extra, _, _), chord)| (extra, chord))
+        .collect()
+}
+
+fn default_spelling(pc: u8) -> Note {
+    let (literal, modifier) = match pc {
+        0 => (NoteLiteral::C, None),
+        1 => (NoteLiteral::D, Some(Modifier::Flat)),
+        2 => (NoteLiteral::D, None),
+        3 => (NoteLiteral::E, Some(Modifier::Flat)),
+        4 => (NoteLiteral::E, None),
+        5 => (NoteLiteral::F, None),
+        6 => (NoteLiteral::F, Some(Modifier::Sharp)),
+        7 => (NoteLiteral::G, None),
+        8 => (NoteLiteral::A, Some(Modifier::Flat)),
+        9 => (NoteLiteral::A, None),
+        10 => (NoteLiteral::B, Some(Modifier::Flat)),
+        _ => (NoteLiteral::B, None),
+    };
+    Note::new(literal, modifier)
+}
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use super::{intervals::Interval, Chord};
 
 /// Describes the quality of a chord
-#[derive(Debug, PartialEq, Default, Eq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Default, Eq, Clone, Hash, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum InnerQuality {
     Power,
@@ -19,7 +19,7 @@ pub enum InnerQuality {
     Diminished,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize, Default)]
 #[repr(u8)]
 pub enum Quality {
     #[default]
@@ -32,6 +32,70 @@ pub enum Quality {
 }
 
 impl Quality {
+    /// Returns every quality, in declaration order.
+    /// Meant for listing the qualities, like in a picker, without hard-coding the variants.
+    /// # Returns
+    /// A static slice with all the qualities.
+    pub fn all() -> &'static [Quality] {
+        &[
+            Quality::Major,
+            Quality::Minor,
+            Quality::Dominant,
+            Quality::Diminished,
+            Quality::Augmented,
+            Quality::Power,
+        ]
+    }
+
+    /// Returns the descriptor of the simplest chord of this quality, as it would follow the root in a chord symbol.
+    /// Appending it to any root gives an input the parser accepts and classifies with this quality.
+    /// # Arguments
+    /// * `self` - The quality to get the symbol for.
+    /// # Returns
+    /// The descriptor, empty for `Major`.
+    pub fn default_symbol(&self) -> &'static str {
+        match self {
+            Quality::Major => "",
+            Quality::Minor => "min",
+            Quality::Dominant => "7",
+            Quality::Diminished => "dim",
+            Quality::Augmented => "+",
+            Quality::Power => "5",
+        }
+    }
+
+    /// Returns a user-facing name for the quality, like `Dominant 7th`, meant for labels rather than chord symbols.
+    /// See [crate::chord::Chord::quality_name] for a finer name taking the whole chord into account.
+    /// # Arguments
+    /// * `self` - The quality to name.
+    /// # Returns
+    /// The name of the quality.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Quality::Major => "Major",
+            Quality::Minor => "Minor",
+            Quality::Dominant => "Dominant 7th",
+            Quality::Diminished => "Diminished",
+            Quality::Augmented => "Augmented",
+            Quality::Power => "Power chord",
+        }
+    }
+
+    /// Classifies an arbitrary list of intervals, without building a chord.
+    /// The root is always taken as present, so it can be left out of `intervals`.
+    /// # Arguments
+    /// * `intervals` - The intervals of the chord relative to its root.
+    /// # Returns
+    /// The quality of the chord those intervals form.
+    pub fn identify(intervals: &[Interval]) -> Quality {
+        let mut rbs = [false; 24];
+        rbs[0] = true;
+        for i in intervals {
+            rbs[i.st() as usize] = true;
+        }
+        Quality::quality(&rbs)
+    }
+
     pub fn quality(rbs: &[bool; 24]) -> Quality {
         if Quality::is_dim(rbs) {
             return Quality::Diminished;
@@ -82,6 +146,26 @@ impl Quality {
 }
 
 impl InnerQuality {
+    /// Returns a user-facing name for the quality, like `Minor/Major 7th`.
+    /// # Arguments
+    /// * `self` - The quality to name.
+    /// # Returns
+    /// The name of the quality.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            InnerQuality::Power => "Power chord",
+            InnerQuality::Major => "Major",
+            InnerQuality::Major6 => "Major 6th",
+            InnerQuality::Major7 => "Major 7th",
+            InnerQuality::Minor => "Minor",
+            InnerQuality::Minor6 => "Minor 6th",
+            InnerQuality::Minor7 => "Minor 7th",
+            InnerQuality::MinorMaj7 => "Minor/Major 7th",
+            InnerQuality::Dominant => "Dominant 7th",
+            InnerQuality::Diminished => "Diminished",
+        }
+    }
+
     /// Given a chord, returns its quality
     pub fn from_chord(ch: &Chord) -> InnerQuality {
         let maj6 = ch.has(Interval::MajorSixth);
@@ -127,7 +211,13 @@ impl InnerQuality {
 mod test {
     use test_case::test_case;
 
-    use crate::{chord::quality::InnerQuality, parsing::Parser};
+    use crate::{
+        chord::{
+            intervals::Interval,
+            quality::{InnerQuality, Quality},
+        },
+        parsing::Parser,
+    };
 
     #[test_case("C5", InnerQuality::Power)]
     #[test_case("C6Maj7", InnerQuality::Major6)]
@@ -185,4 +275,57 @@ mod test {
             }
         }
     }
+
+    #[test_case(&[Interval::MajorThird, Interval::PerfectFifth], Quality::Major)]
+    #[test_case(&[Interval::Unison, Interval::MinorThird, Interval::PerfectFifth], Quality::Minor)]
+    #[test_case(&[Interval::MajorThird, Interval::PerfectFifth, Interval::MinorSeventh], Quality::Dominant)]
+    #[test_case(&[Interval::MinorThird, Interval::DiminishedFifth], Quality::Diminished)]
+    #[test_case(&[Interval::MajorThird, Interval::AugmentedFifth], Quality::Augmented)]
+    #[test_case(&[Interval::PerfectFifth], Quality::Power)]
+    fn test_identify(intervals: &[Interval], expected: Quality) {
+        assert_eq!(Quality::identify(intervals), expected);
+    }
+
+    #[test_case("C")]
+    #[test_case("F#")]
+    #[test_case("Bb")]
+    fn test_default_symbols(root: &str) {
+        let mut parser = Parser::new();
+        assert_eq!(Quality::all().len(), 6);
+        for quality in Quality::all() {
+            let input = format!("{root}{}", quality.default_symbol());
+            let chord = parser.parse(&input).unwrap();
+            assert_eq!(&chord.quality, quality);
+        }
+    }
+
+    #[test_case("Cmi7b5")]
+    #[test_case("C7(#9,b13)")]
+    #[test_case("CMaj9#11")]
+    #[test_case("C+")]
+    #[test_case("Csus2")]
+    fn test_identify_matches_parser(input: &str) {
+        let mut parser = Parser::new();
+        let chord = parser.parse(input).unwrap();
+        assert_eq!(Quality::identify(&chord.real_intervals), chord.quality);
+    }
+
+    #[test]
+    fn test_display_names() {
+        let names = Quality::all()
+            .iter()
+            .map(|q| q.display_name())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "Major",
+                "Minor",
+                "Dominant 7th",
+                "Diminished",
+                "Augmented",
+                "Power chord"
+            ]
+        );
+    }
 }
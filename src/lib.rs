@@ -29,13 +29,16 @@
 //! The voicing is generated in a range from C1 to G4.  The generator function accepts a lead note to generate the voicings around it, which allows chaining distinct chords smoothly.
 //!
 //!
+//! # [Scales](#scales)
+//! The scales module returns the notes of the major and natural minor scales of a root, spelled like chord notes are.
+//!
 //! # [Limitations](#limitations)
 //! - Parsed chord notes have enharmonically correct names when possible (for example, a `B#9` chord will have `C𝄪` as the ninth instead of D), but triple flat/sharps are not suported.
 //! - When transposed, slash-bass notes (like C in Ab/C) may not be enharmonically correct.
-//! - The parser is not customizable for now, but it is expected to be in the future, for example:
+//! - The parser can be customized through a [ParserConfig](parsing/parser_config/struct.ParserConfig.html), but only with a few options for now. More are expected in the future, for example:
 //!     - Include or remove both custom and default validators.
 //!     - Include or remove sets of allowed symbols.
-//!     - Maybe allow other notations like Latin or German.
+//!     - Maybe allow other notations like German (Latin solfège is already supported, see `solfege_notes`).
 //!
 //! # [Examples](#examples)
 //! ```rust
@@ -57,4 +60,5 @@
 
 pub mod chord;
 pub mod parsing;
+pub mod scales;
 pub mod voicings;
@@ -7,21 +7,24 @@ use crate::{
         Chord,
     },
     parsing::{
-        expressions::{BassExp, OmitExp, PowerExp},
+        expressions::{AddExp, BassExp, OmitExp, PowerExp},
         parser_error::ParserErrors,
     },
 };
 
-use super::{expression::Exp, parser_error::ParserError};
+use super::{expression::Exp, parser_config::ParserConfig, parser_error::ParserError};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Ast {
     pub(crate) root: Note,
+    /// Position of the last character of the root spelling in the input.
+    pub(crate) root_end: usize,
     pub(crate) bass: Option<Note>,
     pub(crate) expressions: Vec<Exp>,
     pub(crate) intervals: Vec<Interval>,
     pub(crate) is_sus: bool,
     pub(crate) errors: Vec<ParserError>,
+    pub(crate) config: ParserConfig,
 }
 
 impl Ast {
@@ -37,15 +40,24 @@ impl Ast {
                 self.is_sus = true;
             }
             Exp::Maj(maj) => maj.execute(&mut self.intervals, &self.expressions),
-            Exp::Extension(ext) => {
-                ext.execute(&mut self.intervals, &mut self.is_sus, &self.expressions)
-            }
+            Exp::Extension(ext) => ext.execute(
+                &mut self.intervals,
+                &mut self.is_sus,
+                &self.expressions,
+                &self.config,
+            ),
             Exp::Add(add) => add.execute(&mut self.intervals),
             Exp::Aug(aug) => aug.execute(&mut self.intervals, &self.expressions),
             Exp::SlashBass(bass) => self.bass = Some(bass.note.clone()),
             Exp::Alt(alt) => alt.execute(&mut self.intervals),
             Exp::Power(pw) => {
-                if self.expressions.len() != 1 {
+                // A slash bass does not change the chord itself, so C5/G is still a power chord
+                let count = self
+                    .expressions
+                    .iter()
+                    .filter(|e| !matches!(e, Exp::SlashBass(_)))
+                    .count();
+                if count != 1 {
                     self.errors.push(ParserError::InvalidPowerExpression);
                 } else {
                     pw.execute(&mut self.intervals)
@@ -98,7 +110,10 @@ impl Ast {
         }
     }
 
-    /// Checks if there are any three consecutive semitones, which are illegal.
+    /// Checks if there are any three consecutive semitones, which are illegal.  
+    /// Pitch classes are compared modulo octave, so extensions are folded down (a b9 counts as a b2).  
+    /// The check wraps around the octave, meaning that B, C and Db in `CMaj7b9` are consecutive.  
+    /// Every offending group is reported with the names of its notes.
     fn validate_semitones(&mut self) -> bool {
         let mut is_valid = true;
         let mut count = 0u16; // Use a 16-bit integer to represent 12 semitones
@@ -110,6 +125,12 @@ impl Ast {
             intervals[pos as usize] = Some(s);
         }
 
+        let root = self.root.clone();
+        let note_name = |i: &Interval| {
+            root.get_note(i.st(), i.to_semantic_interval().numeric())
+                .to_string()
+        };
+
         for i in 0..12 {
             let a = (i + 1) % 12;
             let b = (i + 2) % 12;
@@ -117,9 +138,9 @@ impl Ast {
                 is_valid = false;
                 self.errors
                     .push(ParserError::ThreeConsecutiveSemitones(vec![
-                        format!("{}", intervals[i].unwrap()),
-                        format!("{}", intervals[a].unwrap()),
-                        format!("{}", intervals[b].unwrap()),
+                        note_name(intervals[i].unwrap()),
+                        note_name(intervals[a].unwrap()),
+                        note_name(intervals[b].unwrap()),
                     ]));
             }
         }
@@ -127,51 +148,51 @@ impl Ast {
         is_valid
     }
 
-    fn has_inconsistent_extension(&self, int: &Interval, matches: Vec<&Interval>) -> bool {
-        for i in matches {
-            if self.intervals.contains(i) && self.intervals.contains(int) {
-                return true;
-            }
-        }
-        false
+    /// Returns the position of the extension or add target that brought `int` into the chord.
+    /// Intervals implied by a quality symbol or by another extension have no position.
+    fn interval_pos(&self, int: Interval) -> Option<usize> {
+        self.expressions.iter().find_map(|exp| match exp {
+            Exp::Extension(ext) if ext.interval == int => Some(ext.pos),
+            Exp::Add(add) if add.interval == int => Some(add.target_pos),
+            _ => None,
+        })
     }
 
-    /// Finds illegal extensions combinations (for example 9 and b9/#9)
+    /// Finds illegal extensions combinations (for example 9 and b9/#9).
+    /// A written 7 counts even if it did not make it into the chord, like in `C7bb7`, unless it belongs to a maj7.
+    /// The error names both intervals in input order, and points at the second one.
+    /// Intervals implied by a quality symbol are taken as coming first, and if neither interval was written explicitly
+    /// the error points at the start of the descriptor.
     fn has_inconsistent_extensions(&mut self) -> bool {
-        if self.has_inconsistent_extension(
-            &Interval::Ninth,
-            vec![&Interval::FlatNinth, &Interval::SharpNinth],
-        ) {
-            self.errors.push(ParserError::InconsistentExtension(
-                Interval::Ninth.to_string(),
-            ));
-            return true;
-        }
-        if self.has_inconsistent_extension(&Interval::Eleventh, vec![&Interval::SharpEleventh]) {
-            self.errors.push(ParserError::InconsistentExtension(
-                Interval::Eleventh.to_string(),
-            ));
-            return true;
-        }
-        if self.has_inconsistent_extension(&Interval::Thirteenth, vec![&Interval::FlatThirteenth]) {
-            self.errors.push(ParserError::InconsistentExtension(
-                Interval::Thirteenth.to_string(),
-            ));
-            return true;
-        }
-        if self.has_inconsistent_extension(&Interval::MajorSixth, vec![&Interval::MinorSixth]) {
-            self.errors.push(ParserError::InconsistentExtension(
-                Interval::MajorSixth.to_string(),
-            ));
-            return true;
-        }
-        if self.has_inconsistent_extension(&Interval::MajorThird, vec![&Interval::MinorThird]) {
-            self.errors.push(ParserError::InconsistentExtension(
-                Interval::MajorThird.to_string(),
-            ));
-            return true;
-        }
-        false
+        let pairs = [
+            (Interval::Ninth, Interval::FlatNinth),
+            (Interval::Ninth, Interval::SharpNinth),
+            (Interval::Eleventh, Interval::SharpEleventh),
+            (Interval::Thirteenth, Interval::FlatThirteenth),
+            (Interval::MajorSixth, Interval::MinorSixth),
+            (Interval::MajorThird, Interval::MinorThird),
+            (Interval::MinorSeventh, Interval::DiminishedSeventh),
+        ];
+        let written_seventh = self.interval_pos(Interval::MinorSeventh).is_some()
+            && !self.expressions.iter().any(|e| matches!(e, Exp::Maj(_)));
+        let has = |i: &Interval| {
+            self.intervals.contains(i) || (*i == Interval::MinorSeventh && written_seventh)
+        };
+        let Some((a, b)) = pairs.into_iter().find(|(a, b)| has(a) && has(b)) else {
+            return false;
+        };
+        let (a_pos, b_pos) = (self.interval_pos(a), self.interval_pos(b));
+        let (first, second, pos) = if a_pos <= b_pos {
+            (a, b, b_pos)
+        } else {
+            (b, a, a_pos)
+        };
+        self.errors.push(ParserError::InconsistentExtension {
+            first: first.to_string(),
+            second: second.to_string(),
+            pos: pos.unwrap_or(self.root_end + 1),
+        });
+        true
     }
 
     /// Validates extensions finding for duplicates and incosistencies.
@@ -189,7 +210,6 @@ impl Ast {
                     | Interval::MajorSecond
                     | Interval::MinorThird
                     | Interval::MajorThird
-                    | Interval::DiminishedSeventh
                     | Interval::MajorSeventh => {
                         self.errors.push(ParserError::InvalidExtension(ext.pos));
                         return false;
@@ -235,13 +255,60 @@ impl Ast {
         is_valid
     }
 
+    /// Finds thirds added to a sus chord, which are rejected when [ParserConfig::reject_sus_with_third] is set.
+    /// Every offending add is reported at the position of its target.
+    fn validate_sus(&mut self) -> bool {
+        if !self.config.reject_sus_with_third
+            || !self.expressions.iter().any(|e| matches!(e, Exp::Sus(_)))
+        {
+            return true;
+        }
+        let mut is_valid = true;
+        for exp in &self.expressions {
+            if let Exp::Add(AddExp {
+                interval: Interval::MajorThird | Interval::MinorThird,
+                target_pos,
+            }) = exp
+            {
+                self.errors.push(ParserError::SusWithThird(*target_pos));
+                is_valid = false;
+            }
+        }
+        is_valid
+    }
+
     /// Analizes expressions and intervals finding inconsistencies.  
     /// If any inconcistence is found, self.errors is populated and false is returned.
     fn is_valid(&mut self) -> bool {
         let valid_exp = self.validate_expressions();
         let valid_ext = self.validate_extensions();
         let valid_sem = self.validate_semitones();
-        valid_exp && valid_ext && valid_sem && self.errors.is_empty()
+        let valid_sus = self.validate_sus();
+        valid_exp && valid_ext && valid_sem && valid_sus && self.errors.is_empty()
+    }
+
+    /// Finds the adds whose interval would be in the chord anyway, like the 3 in `Cmaj7add3`.
+    /// Every add is checked by evaluating the expressions again without it.
+    fn get_redundant_adds(&self) -> Vec<Interval> {
+        self.expressions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, exp)| match exp {
+                Exp::Add(add) => {
+                    let mut ast = Ast {
+                        expressions: self.expressions.clone(),
+                        config: self.config.clone(),
+                        ..Default::default()
+                    };
+                    ast.expressions.remove(index);
+                    ast.set_intervals();
+                    ast.intervals
+                        .contains(&add.interval)
+                        .then_some(add.interval)
+                }
+                _ => None,
+            })
+            .collect()
     }
 
     /// Get the notes of the chord
@@ -256,15 +323,13 @@ impl Ast {
         notes
     }
 
-    pub fn get_descriptor(&mut self, name: &str) -> String {
-        let modifier_str = match &self.root.modifier {
-            Some(m) => m.to_string(),
-            None => "".to_string(),
-        };
-        name.replace(&format!("{}{}", self.root.literal, modifier_str), "")
+    /// Get the descriptor of the chord, which is everything in the input after the root spelling.
+    pub fn get_descriptor(&self, input: &str) -> String {
+        let descriptor = input.chars().skip(self.root_end).collect::<String>();
+        collapse_whitespace(&descriptor)
     }
 
-    pub(crate) fn build_chord(&mut self, name: &str) -> Result<Chord, ParserErrors> {
+    pub(crate) fn build_chord(&mut self, input: &str) -> Result<Chord, ParserErrors> {
         self.set_intervals();
         let notes = self.get_notes();
         let mut semitones = Vec::new();
@@ -282,31 +347,42 @@ impl Ast {
         if !self.is_valid() {
             return Err(ParserErrors::new(self.errors.clone()));
         }
+        let redundant_adds = self.get_redundant_adds();
 
-        Ok(Chord::builder(name, self.root.clone())
-            .descriptor(&self.get_descriptor(name))
-            .bass(self.bass.clone())
-            .notes(notes)
-            .note_literals(note_literals)
-            .rbs(rbs)
-            .semitones(semitones)
-            .semantic_intervals(semantic_intervals)
-            .real_intervals(self.intervals.clone())
-            .is_sus(self.is_sus)
-            .adds(vec![])
-            .build())
+        Ok(
+            Chord::builder(&collapse_whitespace(input), self.root.clone())
+                .descriptor(&self.get_descriptor(input))
+                .bass(self.bass.clone())
+                .notes(notes)
+                .note_literals(note_literals)
+                .rbs(rbs)
+                .semitones(semitones)
+                .semantic_intervals(semantic_intervals)
+                .real_intervals(self.intervals.clone())
+                .is_sus(self.is_sus)
+                .adds(vec![])
+                .redundant_adds(redundant_adds)
+                .build(),
+        )
     }
 }
 
+/// Trims the input and collapses inner runs of whitespace into a single space.
+fn collapse_whitespace(input: &str) -> String {
+    input.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
 impl Default for Ast {
     fn default() -> Ast {
         Ast {
             root: Note::new(NoteLiteral::C, None),
+            root_end: 0,
             bass: None,
             expressions: Vec::new(),
             intervals: vec![Interval::Unison],
             is_sus: false,
             errors: Vec::new(),
+            config: ParserConfig::default(),
         }
     }
 }
@@ -1,3 +1,8 @@
+//! # Parsed expressions
+//! Every modifier read by the parser becomes an [Exp], which is later evaluated to get the intervals of the chord.
+//! These types are exposed for inspection (see [Parser::last_expressions](super::Parser::last_expressions))
+//! and follow the internals of the parser, so they are not considered stable.
+//! Only their data is public: evaluating and validating them is left to the parser.
 use std::fmt::{Display, Formatter};
 
 use super::expressions::{
@@ -25,7 +30,7 @@ pub enum Exp {
 }
 
 impl Exp {
-    pub fn validate(&self) -> (bool, usize) {
+    pub(crate) fn validate(&self) -> (bool, usize) {
         match self {
             Exp::Omit(exp) => exp.isvalid(),
             Exp::Add(exp) => exp.isvalid(),
@@ -52,7 +57,7 @@ impl Exp {
         }
     }
 
-    pub fn priority(&self) -> u32 {
+    pub(crate) fn priority(&self) -> u32 {
         match self {
             Exp::Power(_) => 0,
             Exp::Alt(_) => 1,
@@ -70,7 +75,7 @@ impl Exp {
             Exp::SlashBass(_) => 13,
         }
     }
-    pub fn from_priority(p: u32) -> String {
+    pub(crate) fn from_priority(p: u32) -> String {
         match p {
             0 => "5".to_string(),
             1 => "Alt".to_string(),
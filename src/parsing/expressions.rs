@@ -1,8 +1,10 @@
+//! # Expression types
+//! The data of each kind of [Exp](super::expression::Exp), along with its evaluation rules.
 use core::panic;
 
 use crate::chord::{intervals::Interval, note::Note};
 
-use super::expression::Exp;
+use super::{expression::Exp, parser_config::ParserConfig};
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ExtensionExp {
     pub interval: Interval,
@@ -37,11 +39,18 @@ impl ExtensionExp {
             i.push(Interval::Eleventh);
         }
     }
-    pub fn execute(&self, i: &mut Vec<Interval>, is_sus: &mut bool, exp: &[Exp]) {
+    pub(crate) fn execute(
+        &self,
+        i: &mut Vec<Interval>,
+        is_sus: &mut bool,
+        exp: &[Exp],
+        config: &ParserConfig,
+    ) {
         match self.interval {
             Interval::PerfectFourth
             | Interval::AugmentedFourth
             | Interval::MinorSixth
+            | Interval::DiminishedSeventh
             | Interval::FlatNinth
             | Interval::SharpNinth
             | Interval::SharpEleventh
@@ -94,21 +103,31 @@ impl ExtensionExp {
                 }
             }
             Interval::Ninth => {
-                self.include_seventh(i);
-                i.push(Interval::Ninth);
+                if config.imply_extensions {
+                    self.include_seventh(i);
+                }
+                if !i.contains(&self.interval) {
+                    i.push(self.interval);
+                }
             }
             Interval::Eleventh => {
-                self.include_seventh(i);
-                self.include_ninth(i);
+                if config.imply_extensions {
+                    self.include_seventh(i);
+                    self.include_ninth(i);
+                }
                 if !i.contains(&self.interval) {
                     i.push(self.interval);
                 }
-                *is_sus = !i.contains(&Interval::MinorThird);
+                if !config.eleventh_keeps_third {
+                    *is_sus = !i.contains(&Interval::MinorThird);
+                }
             }
             Interval::Thirteenth => {
-                self.include_seventh(i);
-                self.include_ninth(i);
-                self.include_eleventh(i);
+                if config.imply_extensions {
+                    self.include_seventh(i);
+                    self.include_ninth(i);
+                    self.include_eleventh(i);
+                }
                 if !i.contains(&self.interval) {
                     i.push(self.interval);
                 }
@@ -131,7 +150,7 @@ impl AddExp {
             target_pos,
         }
     }
-    pub fn isvalid(&self) -> (bool, usize) {
+    pub(crate) fn isvalid(&self) -> (bool, usize) {
         (
             matches!(
                 self.interval,
@@ -153,7 +172,7 @@ impl AddExp {
         )
     }
 
-    pub fn execute(&self, i: &mut Vec<Interval>) {
+    pub(crate) fn execute(&self, i: &mut Vec<Interval>) {
         if !i.contains(&self.interval) {
             i.push(self.interval);
         }
@@ -169,7 +188,7 @@ impl SusExp {
     pub fn new(interval: Interval) -> Self {
         Self { interval }
     }
-    pub fn execute(&self, i: &mut Vec<Interval>) {
+    pub(crate) fn execute(&self, i: &mut Vec<Interval>) {
         let interval = match self.interval {
             Interval::MinorSecond => Interval::FlatNinth,
             Interval::MajorSecond => Interval::Ninth,
@@ -195,7 +214,7 @@ impl OmitExp {
             target_pos,
         }
     }
-    pub fn isvalid(&self) -> (bool, usize) {
+    pub(crate) fn isvalid(&self) -> (bool, usize) {
         (
             matches!(self.interval, Interval::MajorThird | Interval::PerfectFifth),
             self.target_pos,
@@ -218,7 +237,7 @@ pub struct BassExp;
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct DimExp;
 impl DimExp {
-    pub fn execute(&self, i: &mut Vec<Interval>, exp: &[Exp]) {
+    pub(crate) fn execute(&self, i: &mut Vec<Interval>, exp: &[Exp]) {
         if !i.contains(&Interval::MinorThird)
             && !exp.iter().any(|e| {
                 matches!(
@@ -251,7 +270,7 @@ impl DimExp {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Dim7Exp;
 impl Dim7Exp {
-    pub fn execute(&self, i: &mut Vec<Interval>, exp: &[Exp]) {
+    pub(crate) fn execute(&self, i: &mut Vec<Interval>, exp: &[Exp]) {
         if !i.contains(&Interval::MinorThird)
             && !exp.iter().any(|e| {
                 matches!(
@@ -287,7 +306,7 @@ impl Dim7Exp {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct HalfDimExp;
 impl HalfDimExp {
-    pub fn execute(&self, i: &mut Vec<Interval>, exp: &[Exp]) {
+    pub(crate) fn execute(&self, i: &mut Vec<Interval>, exp: &[Exp]) {
         if !i.contains(&Interval::MinorThird)
             && !exp.iter().any(|e| {
                 matches!(
@@ -323,7 +342,7 @@ impl HalfDimExp {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct MajExp;
 impl MajExp {
-    pub fn execute(&self, i: &mut Vec<Interval>, exp: &[Exp]) {
+    pub(crate) fn execute(&self, i: &mut Vec<Interval>, exp: &[Exp]) {
         if exp.iter().any(|e| {
             matches!(
                 e,
@@ -350,7 +369,7 @@ impl MajExp {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct MinorExp;
 impl MinorExp {
-    pub fn execute(&self, i: &mut Vec<Interval>, exp: &[Exp]) {
+    pub(crate) fn execute(&self, i: &mut Vec<Interval>, exp: &[Exp]) {
         if !i.contains(&Interval::MinorThird)
             && !exp.iter().any(|e| {
                 matches!(
@@ -370,7 +389,7 @@ impl MinorExp {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct AugExp;
 impl AugExp {
-    pub fn execute(&self, i: &mut Vec<Interval>, exp: &[Exp]) {
+    pub(crate) fn execute(&self, i: &mut Vec<Interval>, exp: &[Exp]) {
         if !i.contains(&Interval::AugmentedFifth)
             && !exp.iter().any(|e| {
                 matches!(
@@ -389,7 +408,7 @@ impl AugExp {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct AltExp;
 impl AltExp {
-    pub fn execute(&self, i: &mut Vec<Interval>) {
+    pub(crate) fn execute(&self, i: &mut Vec<Interval>) {
         i.push(Interval::MinorSeventh);
         i.push(Interval::FlatNinth);
         i.push(Interval::SharpNinth);
@@ -401,7 +420,7 @@ impl AltExp {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct PowerExp;
 impl PowerExp {
-    pub fn execute(&self, i: &mut Vec<Interval>) {
+    pub(crate) fn execute(&self, i: &mut Vec<Interval>) {
         i.push(Interval::PerfectFifth);
     }
 }
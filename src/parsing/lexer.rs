@@ -9,6 +9,8 @@ pub struct Lexer {
     current: usize,
     reg_alt: Regex,
     input_len: usize,
+    solfege: bool,
+    words: bool,
 }
 
 impl Lexer {
@@ -20,11 +22,28 @@ impl Lexer {
             tokens: Vec::new(),
             current: 0,
             reg_alt,
+            solfege: false,
+            words: false,
         }
     }
 
+    /// Creates a lexer that reads notes as solfège syllables instead of letters,
+    /// see [ParserConfig::solfege_notes](super::parser_config::ParserConfig::solfege_notes).
+    pub fn with_solfege(solfege: bool) -> Lexer {
+        Lexer {
+            solfege,
+            ..Lexer::new()
+        }
+    }
+
+    /// Makes the lexer read whole spelled-out words like `minor` or `seven`,
+    /// see [ParserConfig::spelled_out_words](super::parser_config::ParserConfig::spelled_out_words).
+    pub fn with_words(self, words: bool) -> Lexer {
+        Lexer { words, ..self }
+    }
+
     pub fn scan_tokens(&mut self, source: &str) -> Vec<Token> {
-        self.input_len = source.len();
+        self.input_len = source.chars().count();
         let mut iter = source.chars().peekable();
         while !self.is_at_end() {
             self.scan_token(&mut iter);
@@ -47,13 +66,13 @@ impl Lexer {
             Some(c) => match c {
                 '#' | '♯' => self.add_token(TokenType::Sharp, self.current, 1),
                 '♭' => self.add_token(TokenType::Flat, self.current, 1),
-                '△' | '^' => self.add_token(TokenType::Maj7, self.current, 1),
+                '△' | 'Δ' | '^' => self.add_token(TokenType::Maj7, self.current, 1),
                 '-' => self.add_token(TokenType::Hyphen, self.current, 1),
                 '°' => self.add_token(TokenType::Dim, self.current, 1),
                 'ø' => self.add_token(TokenType::HalfDim, self.current, 1),
                 '/' => self.add_token(TokenType::Slash, self.current, 1),
                 '+' => self.add_token(TokenType::Aug, self.current, 1),
-                ' ' => (),
+                c if c.is_whitespace() => (),
                 ',' => self.add_token(TokenType::Comma, self.current, 1),
                 '(' => self.add_token(TokenType::LParent, self.current, 1),
                 ')' => self.add_token(TokenType::RParent, self.current, 1),
@@ -85,6 +104,12 @@ impl Lexer {
                             let p = chars.peek();
                             cond = p.is_some_and(|p| self.is_alphabetic(p));
                         }
+                        if self.words {
+                            if let Some(token_type) = TokenType::from_word(&literal) {
+                                self.add_word(token_type, pos, literal.len());
+                                return;
+                            }
+                        }
                         self.parse_string(&literal, pos);
                     } else {
                         self.add_token(TokenType::Illegal, self.current, 1);
@@ -102,6 +127,12 @@ impl Lexer {
     /// The parsing is done by checking first all the string and advancing the start to ensure that the last longest match is found.  
     /// For example, for `Cminomit5`, those are the handled parts:  
     /// `Cminomit` -> `minomit` -> `inomit` -> `omit` (match!) -> `Cmin` -> `min` (match!) -> `C` (match!)  
+    ///
+    /// So words are split from their end, taking each time the longest keyword that ends the remaining part.
+    /// For the major keyword this means that `Cmajor`, `Cmaj`, `Cma` and `CM` are all read as `C` + major, since the whole
+    /// keyword is always found before any shorter one, while a partial keyword leaves illegal letters behind
+    /// (in `Cmajj7` the second `j` is illegal). Keywords are case sensitive, so an upper case letter inside a word can
+    /// be read as a note, as the `A` in `CmA7`.
     fn parse_string(&mut self, s: &str, pos: usize) {
         let mut start = 0;
         let mut end = s.len();
@@ -109,7 +140,7 @@ impl Lexer {
         let mut errors = Vec::new();
         while end > 0 {
             let substring = &s[start..end];
-            if let Some(m) = TokenType::from_string(substring) {
+            if let Some(m) = self.keyword(substring) {
                 tokens.push((m, pos + start, substring.len()));
                 end = start;
                 start = 0;
@@ -134,6 +165,30 @@ impl Lexer {
         }
     }
 
+    /// Matches a keyword or a note. In solfège mode syllables take precedence, so `Mi` is a note
+    /// rather than minor, and single letters are not notes.
+    fn keyword(&self, s: &str) -> Option<TokenType> {
+        if !self.solfege {
+            return TokenType::from_string(s);
+        }
+        TokenType::from_solfege(s).or_else(|| match TokenType::from_string(s) {
+            Some(TokenType::Note(_)) => None,
+            token => token,
+        })
+    }
+
+    /// Adds the token of a spelled-out word, dropping the `diminished` of `half diminished`.
+    fn add_word(&mut self, token_type: TokenType, pos: usize, len: usize) {
+        let after_half = self
+            .tokens
+            .last()
+            .is_some_and(|t| t.token_type == TokenType::HalfDim);
+        if token_type == TokenType::Dim && after_half {
+            return;
+        }
+        self.add_token(token_type, pos, len);
+    }
+
     fn parse_number(&mut self, s: &str, pos: usize) {
         let mut start = 0;
         let mut end = s.len();
@@ -1,12 +1,13 @@
 //! # Chord parsing module
 pub(crate) mod ast;
-pub(crate) mod expression;
-pub(crate) mod expressions;
+pub mod expression;
+pub mod expressions;
 pub(crate) mod lexer;
+pub mod parser_config;
 pub mod parser_error;
 pub(crate) mod token;
 
-use std::{iter::Peekable, slice::Iter};
+use std::{io::BufRead, iter::Peekable, slice::Iter};
 
 use ast::Ast;
 use expression::Exp;
@@ -15,22 +16,34 @@ use expressions::{
     OmitExp, PowerExp, SlashBassExp, SusExp,
 };
 use lexer::Lexer;
+use parser_config::ParserConfig;
 use parser_error::{ParserError, ParserErrors};
 use token::{Token, TokenType};
 
-use crate::chord::{
-    intervals::Interval,
-    note::{Modifier, Note, NoteLiteral},
-    Chord,
+use crate::{
+    chord::{
+        intervals::Interval,
+        note::{Modifier, Note, NoteLiteral},
+        Chord,
+    },
+    scales::major_scale,
 };
 
+/// The chart sign for repeating the previous chord, accepted by [Parser::parse_all] and [Parser::parse_stream].
+const REPEAT_SIGN: &str = "%";
+/// The chart marks for a section with no chord, accepted by [Parser::parse_progression].
+const NO_CHORD_MARKS: [&str; 2] = ["N.C.", "NC"];
+
 /// This is used to handle X(omit/add a,b) cases.
 /// An omit/add modifier inside a parenthesis changes context to Omit(false)/Add(false).  
 /// When a comma is encountered, if a context exits it is changed to true.    
 /// This allows for handling subsequent tokens assuming this context.  
-/// So in C7(omit3,5), the 5 is assumed as an omit, but in C7(omit3 5) it is not.
+/// So in C7(omit3,5), the 5 is assumed as an omit, but in C7(omit3 5) it is not
+/// (unless [ParserConfig::space_separated_group_items] is set, which makes whitespace act as a comma).
 /// When parents are closed the context is reset to None.  
 /// Commas with no context are ignored.  
+/// With [ParserConfig::comma_separated_extensions] the context is set outside parentheses too,
+/// so `C,add9,11` reads like `C(add9,11)`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Context {
     Omit(bool),
@@ -42,19 +55,36 @@ enum Context {
 /// The parser is responsible fo reading and parsing the user input, transforming it into a [Chord] struct.  
 /// Every time a chord is parsed the parser is cleared, so its recommended to rehuse the parser instead of creating new ones.  
 pub struct Parser {
+    config: ParserConfig,
     lexer: Lexer,
     errors: Vec<ParserError>,
     ast: Ast,
     op_count: i16,
     context: Context,
+    /// Whether each character of the input is whitespace.
+    whitespace: Vec<bool>,
+    /// Expressions read from the last parsed input.
+    last_expressions: Vec<Exp>,
 }
 
 impl Parser {
     pub fn new() -> Parser {
+        Parser::with_config(ParserConfig::default())
+    }
+
+    /// Creates a parser with a custom configuration, see [ParserConfig].
+    pub fn with_config(config: ParserConfig) -> Parser {
+        let lexer = Lexer::with_solfege(config.solfege_notes).with_words(config.spelled_out_words);
         Parser {
-            lexer: Lexer::new(),
+            ast: Ast {
+                config: config.clone(),
+                ..Default::default()
+            },
+            config,
+            whitespace: Vec::new(),
+            last_expressions: Vec::new(),
+            lexer,
             errors: Vec::new(),
-            ast: Ast::default(),
             op_count: 0,
             context: Context::None,
         }
@@ -69,38 +99,356 @@ impl Parser {
     ///   
     /// # Rules
     /// There is a set of semantic and syntactic rules to ensure chord's consistency, for now the parser will reject a chord if:
-    /// - There are no Root.
+    /// - There are no Root (this includes empty and whitespace-only inputs).
     /// - There are multiple roots.
     /// - There are duplicate basses (like C/E/Eb).
     /// - There are two thirds.
     /// - There are two fifths (except for (b5, #5) which is allowed).
     /// - There are contradictory sevenths (like m7 and Maj7) or multiple ones.
     /// - There are illegal alterations (like #2, b4, #6).
+    ///   Double accidentals (`bb` or `##`) are read as a whole, and `bb7` is the only valid one: a diminished seventh,
+    ///   as in `Cmb5bb7`, which can not be written along with a 7. Right after the root they still belong to the note,
+    ///   so `Cbb7` is a C double flat seventh chord.
     /// - An alteration has no target.
     /// - There are duplicate tensions, like 11, #11 (except for (b9, #9), which is allowed).
+    ///   This includes explicit adds: in `C7b9add9` the add does not win over the alteration,
+    ///   and the chord is rejected with an inconsistent ninth just like `C9b9`.
     /// - A sus modifier is not sus2, susb2, sus4 or sus#4.
     /// - An add3 is sharp or flat.
     /// - An Omit modifier has no target (this includes wrong targets: any target which is not a 3 or 5).
+    ///   Omit modifiers can be written as `omit` or `no`, which are synonyms both inside and outside parentheses.
     /// - There are more than one sus modifier.
-    /// - Slash notation is used for anything other than 9 (6/9) or bass notation.
+    /// - Slash notation is used for anything other than 9 (6/9), a major seventh after a minor quality
+    ///   (`Cm/maj7` is `CmMaj7`) or bass notation. The major keyword needs an extension after it,
+    ///   so `Cm/maj` is rejected, and so is `C/maj7`, since there is no minor-major quality to complete.
+    ///   The 9 of `6/9` is an added ninth, so `C6/9`, `C69` and `C6add9` are the same chord (C, E, G, A, D)
+    ///   and all are normalized as `C69`. Nothing can follow the slash form, so `C6/9(#11)` must be written `C69(#11)`.
+    /// - A power chord (5) has anything else than a slash bass (C5/G is allowed, C5add9 is not).
+    /// - There are three notes a semitone apart from each other (like B, C, Db in CMaj7b9), compared by pitch class.
+    ///
+    /// # Suspended chords
+    /// A sus modifier replaces the third: `sus4` (or `sus` alone) with a perfect fourth, and `sus2` with a ninth,
+    /// so `C7sus` and `C7sus4` are C, F, G, Bb while `C7sus2` is C, G, Bb, D.
+    /// Extensions keep their meaning, so `C9sus4` is C, F, G, Bb, D and `C13sus2` is C, G, Bb, D, A,
+    /// and a ninth written along with `sus2` is the same note, so `C9sus2` is the same chord as `C7sus2`.
+    /// Chords suspended on the fourth are normalized with `sus` (`C9sus4` is `C9sus`), while the second of `sus2`
+    /// is named as a ninth with no third (`C7sus2` is `C9(omit3)`).
+    ///
+    /// # Parentheses
+    /// Parentheses group modifiers but do not change their meaning, so a quality can be written inside them:
+    /// `Cm(maj7)` is `CmMaj7`, `C(m7)` is `Cm7` and `C7(sus4)` is `C7sus4`.
+    /// A chord can have several groups, like `C(add9)(b5)`, but groups can not be nested.
+    /// Inside a group, omit/add targets separated by commas share the modifier, as in `C(omit3,5)`.
+    ///
+    /// # Whitespace
+    /// Whitespace never changes the meaning of a chord: `"  Cmaj7  "`, `"C  maj7"` and `"Cmaj7"` are the same chord.
+    /// Leading and trailing whitespace is trimmed and inner runs of whitespace are collapsed into a single space
+    /// for the `origin` and `descriptor` of the resulting chord.
+    /// Error positions still refer to the input as given.
+    /// The only exception is [ParserConfig::space_separated_group_items], which makes whitespace separate group items.
     pub fn parse(&mut self, input: &str) -> Result<Chord, ParserErrors> {
+        let input = &self.strip_annotations(input);
+        if input.trim().is_empty() {
+            self.last_expressions.clear();
+            return Err(ParserErrors::new(vec![ParserError::MissingRootNote]));
+        }
+        self.whitespace = input.chars().map(char::is_whitespace).collect();
         let binding = self.lexer.scan_tokens(input);
         let mut tokens = binding.iter().peekable();
         self.read_root(&mut tokens);
         self.read_tokens(&mut tokens);
-        if !self.errors.is_empty() {
-            return Err(ParserErrors::new(self.errors.clone()));
-        }
-        let res = self.ast.build_chord(input);
+        self.build(input)
+    }
+
+    /// Parses a descriptor (everything after the root of a chord, like `maj7#11` or `m7/G`) over a given root.
+    /// This is meant for inputs where root and quality come separately, and it is not the same as parsing them
+    /// concatenated: the root is never changed by the descriptor, so `#5` over C is `C(#5)` while `C#5` is a C# power chord.
+    /// An empty descriptor gives a major triad.
+    ///
+    /// The origin of the chord is the root followed by the descriptor, and error positions refer to the descriptor alone.
+    /// # Arguments
+    /// * `root` - The root note of the chord.
+    /// * `descriptor` - The descriptor to parse.
+    /// # Returns
+    /// * A Result containing the [Chord] if the parsing was successful, otherwise a [ParserErrors] struct.
+    pub fn parse_descriptor(
+        &mut self,
+        root: Note,
+        descriptor: &str,
+    ) -> Result<Chord, ParserErrors> {
+        let input = &self.strip_annotations(descriptor);
+        self.whitespace = input.chars().map(char::is_whitespace).collect();
+        let binding = self.lexer.scan_tokens(input);
+        let mut tokens = binding.iter().peekable();
+        self.ast.root = root;
+        self.read_tokens(&mut tokens);
+        self.build(input).map(|mut chord| {
+            chord.origin = format!("{}{}", chord.root, chord.origin);
+            chord
+        })
+    }
+
+    /// Builds the chord from the tokens read so far and resets the parser for the next input.
+    fn build(&mut self, input: &str) -> Result<Chord, ParserErrors> {
+        let res = if self.errors.is_empty() {
+            self.ast.build_chord(input)
+        } else {
+            Err(ParserErrors::new(self.errors.clone()))
+        };
+        self.last_expressions = self.ast.expressions.clone();
         self.cleanup();
         res
     }
 
+    /// Parses given input and transposes the resulting chord by a number of semitones.
+    /// This is useful for capos, where the shape being played is not the chord that sounds:
+    /// a `G` shape with a capo on the second fret sounds as `A`.
+    /// See [Chord::transpose_by_semitones] for how the new root is spelled.
+    /// # Arguments
+    /// * `input` - The string to parse.
+    /// * `semitones` - The number of semitones to transpose the chord by, up if positive and down if negative.
+    /// # Returns
+    /// * The transposed chord, or the errors found while parsing.
+    pub fn parse_with_transpose(
+        &mut self,
+        input: &str,
+        semitones: i8,
+    ) -> Result<Chord, ParserErrors> {
+        self.parse(input)
+            .map(|chord| chord.transpose_by_semitones(semitones))
+    }
+
+    /// Parses a chord written as a scale degree of the major key of `key`, like the ones in Roman numeral charts.
+    /// The numeral (`I` to `VII`) gives the root, and can be lowered or raised with `b` or `#` (so `bVI` in C is Ab).
+    /// Anything after the numeral is read as the descriptor of the chord, following the rules of [Parser::parse].
+    ///
+    /// Upper case numerals are major and lower case numerals are minor, so `V7` in C is `G7` and `ii7` is `Dm7`.
+    /// A lower case numeral with a descriptor that already removes the major third is left as is, so `viio7` is `Bo7`
+    /// and `iiø7` is `Dø7`.
+    ///
+    /// The returned chord is the one parsed from the concrete root, so its origin is `Dm7` for `ii7` in C,
+    /// while the positions of the errors refer to the input as given (the `(` of `viio7(` is at position 6).
+    /// # Arguments
+    /// * `input` - The degree to parse.
+    /// * `key` - The tonic of the major key.
+    /// # Returns
+    /// * A Result containing the [Chord] in the given key, otherwise a [ParserErrors] struct.
+    ///   A malformed numeral is reported as [ParserError::InvalidRomanNumeral].
+    pub fn parse_degree(&mut self, input: &str, key: &Note) -> Result<Chord, ParserErrors> {
+        let trimmed = input.trim_start();
+        if trimmed.trim_end().is_empty() {
+            return Err(ParserErrors::new(vec![ParserError::MissingRootNote]));
+        }
+        let offset = input.chars().count() - trimmed.chars().count();
+        let accidentals = trimmed
+            .chars()
+            .take_while(|c| *c == 'b' || *c == '#')
+            .collect::<String>();
+        let rest = &trimmed[accidentals.len()..];
+        let numeral = rest
+            .chars()
+            .take_while(|c| matches!(c, 'I' | 'V' | 'i' | 'v'))
+            .collect::<String>();
+        let descriptor = &rest[numeral.len()..];
+        let error = ParserError::InvalidRomanNumeral(offset + accidentals.len() + 1);
+
+        let is_lower = numeral.chars().all(|c| c.is_lowercase());
+        if !is_lower && !numeral.chars().all(|c| c.is_uppercase()) {
+            return Err(ParserErrors::new(vec![error]));
+        }
+        let degree = ["I", "II", "III", "IV", "V", "VI", "VII"]
+            .iter()
+            .position(|n| *n == numeral.to_uppercase());
+        let (Some(degree), true) = (degree, accidentals.len() <= 1) else {
+            return Err(ParserErrors::new(vec![error]));
+        };
+
+        let mut root = major_scale(key)[degree].clone();
+        match accidentals.as_str() {
+            "b" => root = root.get_note(11, 1),
+            "#" => root = root.get_note(1, 1),
+            _ => (),
+        }
+
+        let root = self.spell(&root);
+        // Errors are found in the rewritten input, where the root takes the place of the accidentals and the numeral
+        let shift =
+            (offset + accidentals.len() + numeral.len()) as isize - root.chars().count() as isize;
+        let shifted = |e: ParserErrors, shift: isize| {
+            ParserErrors::new(e.errors.into_iter().map(|e| e.shifted(shift)).collect())
+        };
+        let chord = self
+            .parse(&format!("{}{}", root, descriptor))
+            .map_err(|e| shifted(e, shift))?;
+        if is_lower && chord.real_intervals.contains(&Interval::MajorThird) {
+            return self
+                .parse(&format!("{}m{}", root, descriptor))
+                .map_err(|e| shifted(e, shift - 1));
+        }
+        Ok(chord)
+    }
+
+    /// Writes a note the way this parser reads it, in solfège if [ParserConfig::solfege_notes] is set.
+    fn spell(&self, note: &Note) -> String {
+        if !self.config.solfege_notes {
+            return note.to_string();
+        }
+        let syllable = match note.literal {
+            NoteLiteral::C => "Do",
+            NoteLiteral::D => "Re",
+            NoteLiteral::E => "Mi",
+            NoteLiteral::F => "Fa",
+            NoteLiteral::G => "Sol",
+            NoteLiteral::A => "La",
+            NoteLiteral::B => "Si",
+        };
+        match &note.modifier {
+            Some(modifier) => format!("{}{}", syllable, modifier),
+            None => syllable.to_string(),
+        }
+    }
+
+    /// Returns the expressions read from the last parsed input, whether it was parsed successfully or not.
+    /// Expressions are sorted in evaluation order once the chord is built.
+    ///
+    /// This is meant for debugging and custom analysis. The [Exp] types mirror the internals of the parser,
+    /// so they can change between minor versions, unlike the [Chord] struct.
+    pub fn last_expressions(&self) -> &[Exp] {
+        &self.last_expressions
+    }
+
+    /// Parses the chords read from a reader, lazily and reusing this parser for all of them.
+    /// The input is split by whitespace and line breaks, so every whitespace separated item is taken as a chord.
+    /// Errors are reported for each chord and do not stop the stream.
+    ///
+    /// Reading stops at the first I/O error, invalid UTF-8 included, and the stream just ends there:
+    /// the error is not reported, so it can not be told apart from the end of the input.
+    /// To handle read errors, read the lines yourself and parse them with [Parser::parse_all].
+    /// A `%` repeats the last chord parsed successfully, as in charts (see [Parser::parse_all]).
+    /// # Arguments
+    /// * `reader` - The reader to read the chords from.
+    /// # Returns
+    /// * An iterator over the result of parsing each chord.
+    pub fn parse_stream<'a, R: BufRead + 'a>(
+        &'a mut self,
+        reader: R,
+    ) -> impl Iterator<Item = Result<Chord, ParserErrors>> + 'a {
+        reader
+            .lines()
+            .map_while(Result::ok)
+            .flat_map(|line| {
+                line.split_whitespace()
+                    .map(String::from)
+                    .collect::<Vec<String>>()
+            })
+            .scan(None, move |previous, chord| {
+                Some(self.parse_or_repeat(&chord, previous))
+            })
+    }
+
+    /// Parses a list of chords, reusing this parser for all of them.
+    /// Each input is parsed on its own, so errors in one chord never affect the others.
+    ///
+    /// As in charts, a `%` repeats the last chord parsed successfully, skipping over inputs that failed.
+    /// With no chord to repeat it fails as [Parser::parse] does, since `%` is not a chord by itself.
+    /// # Arguments
+    /// * `inputs` - The chords to parse.
+    /// # Returns
+    /// * The result of parsing each chord, in the same order as `inputs`.
+    pub fn parse_all(&mut self, inputs: &[&str]) -> Vec<Result<Chord, ParserErrors>> {
+        let mut previous = None;
+        inputs
+            .iter()
+            .map(|input| self.parse_or_repeat(input, &mut previous))
+            .collect()
+    }
+
+    /// Parses a chord progression written as in a chart, with its chords separated by whitespace, like `Cmaj7 N.C. G7 %`.
+    /// Each chord is parsed on its own as in [Parser::parse_all], `%` included.
+    ///
+    /// The no-chord marks `N.C.` and `NC` are not errors but sections with no chord, given as `Ok(None)`,
+    /// while chords are given as `Ok(Some(chord))`. A `%` after a no-chord mark repeats it, so it is also `Ok(None)`.
+    /// The marks are only read here: [Parser::parse] still rejects them, since they are not chords.
+    /// # Arguments
+    /// * `input` - The progression to parse.
+    /// # Returns
+    /// * The result of parsing each item of the progression, in order.
+    pub fn parse_progression(&mut self, input: &str) -> Vec<Result<Option<Chord>, ParserErrors>> {
+        let mut previous = None;
+        let mut no_chord = false;
+        input
+            .split_whitespace()
+            .map(|item| {
+                if NO_CHORD_MARKS.contains(&item) || (item == REPEAT_SIGN && no_chord) {
+                    no_chord = true;
+                    return Ok(None);
+                }
+                let res = self.parse_or_repeat(item, &mut previous);
+                if res.is_ok() {
+                    no_chord = false;
+                }
+                res.map(Some)
+            })
+            .collect()
+    }
+
+    /// Parses `input`, or returns `previous` if `input` is the `%` repeat sign and there is a chord to repeat.
+    /// `previous` is updated with every chord parsed successfully.
+    fn parse_or_repeat(
+        &mut self,
+        input: &str,
+        previous: &mut Option<Chord>,
+    ) -> Result<Chord, ParserErrors> {
+        if let (REPEAT_SIGN, Some(chord)) = (input.trim(), &previous) {
+            return Ok(chord.clone());
+        }
+        let res = self.parse(input);
+        if let Ok(chord) = &res {
+            *previous = Some(chord.clone());
+        }
+        res
+    }
+
+    /// Blanks out the groups of the input holding one of [ParserConfig::ignored_annotations].
+    /// Annotations are replaced by whitespace instead of removed, so positions in the input do not change.
+    fn strip_annotations(&self, input: &str) -> String {
+        if self.config.ignored_annotations.is_empty() {
+            return input.to_string();
+        }
+        let mut chars = input.chars().collect::<Vec<char>>();
+        let mut start = None;
+        for i in 0..chars.len() {
+            match chars[i] {
+                '(' => start = Some(i),
+                ')' => {
+                    if let Some(s) = start.take() {
+                        let content = chars[s + 1..i].iter().collect::<String>();
+                        let content = content.trim();
+                        if self
+                            .config
+                            .ignored_annotations
+                            .iter()
+                            .any(|a| a.trim().eq_ignore_ascii_case(content))
+                        {
+                            chars[s..=i].iter_mut().for_each(|c| *c = ' ');
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        chars.into_iter().collect()
+    }
+
     fn cleanup(&mut self) {
         self.errors.clear();
-        self.ast = Ast::default();
+        self.ast = Ast {
+            config: self.config.clone(),
+            ..Default::default()
+        };
         self.op_count = 0;
         self.context = Context::None;
+        self.whitespace.clear();
     }
 
     fn read_root(&mut self, tokens: &mut Peekable<Iter<Token>>) {
@@ -109,8 +457,9 @@ impl Parser {
             None => {
                 self.errors.push(ParserError::MissingRootNote);
             }
-            Some(n) => {
+            Some((n, end)) => {
                 self.ast.root = n;
+                self.ast.root_end = end;
             }
         }
     }
@@ -154,10 +503,13 @@ impl Parser {
             TokenType::Sus => self.sus(tokens),
             TokenType::Minor => self.ast.expressions.push(Exp::Minor(MinorExp)),
             TokenType::Hyphen => self.hyphen(tokens, token.pos),
-            TokenType::Maj => self.ast.expressions.push(Exp::Maj(MajExp)),
-            TokenType::Maj7 => self.maj7(tokens, &token.pos),
+            TokenType::Maj => self.maj(tokens, &token.pos),
+            TokenType::Maj7 if self.config.triangle_implies_seventh => {
+                self.maj7(tokens, &token.pos)
+            }
+            TokenType::Maj7 => self.ast.expressions.push(Exp::Maj(MajExp)),
             TokenType::Slash => self.slash(tokens, token),
-            TokenType::LParent => self.lparen(tokens, token.pos),
+            TokenType::LParent => self.lparen(tokens),
             TokenType::RParent => self.rparen(token.pos),
             TokenType::Comma => self.comma(),
             TokenType::Bass => self.ast.expressions.push(Exp::Bass(BassExp)),
@@ -166,6 +518,14 @@ impl Parser {
         }
     }
 
+    fn maj(&mut self, tokens: &mut Peekable<Iter<Token>>, pos: &usize) {
+        if self.config.bare_major_implies_seventh && !self.expect_extension(tokens) {
+            self.maj7(tokens, pos);
+        } else {
+            self.ast.expressions.push(Exp::Maj(MajExp));
+        }
+    }
+
     fn maj7(&mut self, tokens: &mut Peekable<Iter<Token>>, pos: &usize) {
         self.ast.expressions.push(Exp::Maj(MajExp));
         if !self.expect_peek(TokenType::Extension("7".to_string()), tokens) {
@@ -177,6 +537,21 @@ impl Parser {
     }
 
     fn slash(&mut self, tokens: &mut Peekable<Iter<Token>>, token: &Token) {
+        // After a minor quality, a major seventh after the slash is part of the quality, as in Cm/maj7,
+        // and is read as if there was no slash
+        let minor = self
+            .ast
+            .expressions
+            .iter()
+            .any(|e| matches!(e, Exp::Minor(_)));
+        let mut ahead = tokens.clone();
+        ahead.next();
+        if minor
+            && (self.expect_peek(TokenType::Maj7, tokens)
+                || self.expect_peek(TokenType::Maj, tokens) && self.expect_extension(&mut ahead))
+        {
+            return;
+        }
         if self.expect_extension(tokens) {
             let alt = tokens
                 .next()
@@ -199,7 +574,7 @@ impl Parser {
                     let next = tokens.next().map_or(token.pos, |t| t.pos);
                     self.errors.push(ParserError::IllegalSlashNotation(next));
                 }
-                Some(b) => {
+                Some((b, _)) => {
                     self.ast
                         .expressions
                         .push(Exp::SlashBass(SlashBassExp::new(b)));
@@ -207,19 +582,28 @@ impl Parser {
             }
         }
         if !self.expect_peek(TokenType::Eof, tokens) {
-            let next = tokens.next().map_or(token.pos, |t| t.pos);
-            self.errors.push(ParserError::IllegalSlashNotation(next));
+            let next = tokens.next();
+            self.errors.push(ParserError::IllegalSlashNotation(
+                next.map_or(token.pos, |t| t.pos),
+            ));
+            // A second slash (like in C/G/E) is reported once, at the slash, and not for every note after it
+            if next.is_some_and(|t| t.token_type == TokenType::Slash) {
+                tokens.by_ref().for_each(drop);
+            }
         }
     }
 
-    fn expect_note(&mut self, tokens: &mut Peekable<Iter<Token>>) -> Option<Note> {
+    /// Reads a note and its accidentals if any.
+    /// Returns the note along with the position of its last character in the input.
+    fn expect_note(&mut self, tokens: &mut Peekable<Iter<Token>>) -> Option<(Note, usize)> {
         let note = tokens.next();
         match note {
             None => None,
-            Some(n) => match &n.token_type {
+            Some(token) => match &token.token_type {
                 TokenType::Note(n) => {
-                    let modifier = self.match_modifier(tokens);
-                    Some(Note::new(NoteLiteral::from_string(n), modifier))
+                    let mut end = token.pos + token.len - 1;
+                    let modifier = self.match_note_modifier(tokens, &mut end);
+                    Some((Note::new(NoteLiteral::from_string(n), modifier), end))
                 }
                 _ => None,
             },
@@ -265,7 +649,7 @@ impl Parser {
         self.op_count -= 1;
     }
 
-    fn lparen(&mut self, tokens: &mut Peekable<Iter<Token>>, pos: usize) {
+    fn lparen(&mut self, tokens: &mut Peekable<Iter<Token>>) {
         self.op_count += 1;
         self.context = Context::None;
         while let Some(token) = tokens.next() {
@@ -275,20 +659,68 @@ impl Parser {
                     break;
                 }
                 TokenType::LParent => {
-                    self.errors.push(ParserError::NestedParenthesis(pos));
+                    self.errors.push(ParserError::NestedParenthesis(token.pos));
                 }
                 TokenType::Eof => {
+                    // Point at the end of the input, where the closing parenthesis was expected
                     self.errors
-                        .push(ParserError::MissingClosingParenthesis(pos));
+                        .push(ParserError::MissingClosingParenthesis(token.pos));
                     break;
                 }
                 _ => (),
             }
+            if self.config.space_separated_group_items && self.follows_whitespace(token.pos) {
+                self.context = match self.context {
+                    Context::Omit(_) => Context::Omit(true),
+                    Context::Add(_) => Context::Add(true),
+                    ref context => context.clone(),
+                };
+            }
             // This will advance to next token
             self.process_token(token, tokens);
         }
     }
 
+    /// Reads the accidentals of a note, moving `end` to the last one read.
+    /// A repeated accidental is read as a double one (`Bbb`, `F##`), unless it forms a valid alteration
+    /// with the extension that follows it, so `C##5` is still C# with a sharp fifth.
+    fn match_note_modifier(
+        &self,
+        tokens: &mut Peekable<Iter<Token>>,
+        end: &mut usize,
+    ) -> Option<Modifier> {
+        let (single, double, token_type) = if self.expect_peek(TokenType::Flat, tokens) {
+            (Modifier::Flat, Modifier::DFlat, TokenType::Flat)
+        } else if self.expect_peek(TokenType::Sharp, tokens) {
+            (Modifier::Sharp, Modifier::DSharp, TokenType::Sharp)
+        } else {
+            return None;
+        };
+        let first = tokens
+            .next()
+            .expect("expect_peek guarantees that a next token exist");
+        *end = first.pos;
+        if !self.expect_peek(token_type, tokens) {
+            return Some(single);
+        }
+        let mut ahead = tokens.clone();
+        ahead.next();
+        if let Some(Token {
+            token_type: TokenType::Extension(ext),
+            ..
+        }) = ahead.peek()
+        {
+            if Interval::from_chord_notation(&format!("{}{}", single, ext)).is_some() {
+                return Some(single);
+            }
+        }
+        let second = tokens
+            .next()
+            .expect("expect_peek guarantees that a next token exist");
+        *end = second.pos;
+        Some(double)
+    }
+
     fn match_modifier(&self, tokens: &mut Peekable<Iter<Token>>) -> Option<Modifier> {
         let mut modifier = None;
         if self.expect_peek(TokenType::Flat, tokens) {
@@ -301,6 +733,17 @@ impl Parser {
         modifier
     }
 
+    /// Checks if the character before given 1-based position is whitespace.
+    fn follows_whitespace(&self, pos: usize) -> bool {
+        pos >= 2 && self.whitespace.get(pos - 2).is_some_and(|w| *w)
+    }
+
+    /// Checks if omit/add targets separated by commas share the modifier at this point of the input:
+    /// inside a group, or anywhere with [ParserConfig::comma_separated_extensions].
+    fn in_list(&self) -> bool {
+        self.op_count > 0 || self.config.comma_separated_extensions
+    }
+
     fn comma(&mut self) {
         match self.context {
             Context::Omit(_) => self.context = Context::Omit(true),
@@ -311,7 +754,7 @@ impl Parser {
     }
 
     fn omit(&mut self, token: &Token, tokens: &mut Peekable<Iter<Token>>) {
-        if self.op_count > 0 {
+        if self.in_list() {
             self.context = Context::Omit(false);
         }
         if self.expect_peek(TokenType::Extension("5".to_string()), tokens) {
@@ -334,16 +777,22 @@ impl Parser {
     }
 
     fn add(&mut self, token: &Token, tokens: &mut Peekable<Iter<Token>>) {
-        if self.op_count > 0 {
+        if self.in_list() {
             self.context = Context::Add(false);
         }
         let modifier = self.match_modifier(tokens);
+        let double = modifier
+            .as_ref()
+            .is_some_and(|m| self.match_double_modifier(tokens, m));
         if self.expect_extension(tokens) {
             let next = tokens.next().unwrap();
             if let TokenType::Extension(t) = &next.token_type {
                 let mut id = String::new();
                 if let Some(m) = modifier {
                     id.push_str(m.to_string().as_str());
+                    if double {
+                        id.push_str(m.to_string().as_str());
+                    }
                 }
                 id.push_str(t);
                 let interval = Interval::from_chord_notation(&id);
@@ -374,20 +823,45 @@ impl Parser {
         }
     }
 
+    /// Reads a second accidental repeating `modifier` when an extension follows it,
+    /// so double accidentals like the one in `bb7` apply to the extension as a whole.
+    /// Only one accidental is read, so a third one is left alone and reported as unexpected.
+    fn match_double_modifier(
+        &self,
+        tokens: &mut Peekable<Iter<Token>>,
+        modifier: &Modifier,
+    ) -> bool {
+        let token_type = match modifier {
+            Modifier::Flat => TokenType::Flat,
+            Modifier::Sharp => TokenType::Sharp,
+            _ => return false,
+        };
+        let mut ahead = tokens.clone();
+        ahead.next();
+        if !self.expect_peek(token_type, tokens) || !self.expect_extension(&mut ahead) {
+            return false;
+        }
+        tokens.next();
+        true
+    }
+
     fn modifier(&mut self, tokens: &mut Peekable<Iter<Token>>, modifier: Modifier, token: &Token) {
+        let double = self.match_double_modifier(tokens, &modifier);
         if self.expect_extension(tokens) {
             let alt = tokens
                 .next()
                 .expect("expect_extension guarantees that a next token exist");
             if let TokenType::Extension(a) = &alt.token_type {
                 let mut id = modifier.to_string();
+                if double {
+                    id.push_str(&modifier.to_string());
+                }
                 id.push_str(a);
                 let interval = Interval::from_chord_notation(&id);
                 if let Some(int) = interval {
                     self.add_interval(int, token.pos);
                 } else {
-                    self.errors
-                        .push(ParserError::InvalidExtension(token.pos + 1));
+                    self.errors.push(ParserError::InvalidExtension(alt.pos));
                 }
             }
         } else {
@@ -407,6 +881,10 @@ impl Parser {
                 }
                 _ => {
                     self.context = Context::None;
+                    // sus11 replaces the third even when an eleventh alone would keep it
+                    if int == Interval::Eleventh && self.config.eleventh_keeps_third {
+                        self.ast.is_sus = true;
+                    }
                     self.ast
                         .expressions
                         .push(Exp::Extension(ExtensionExp::new(int, pos)));
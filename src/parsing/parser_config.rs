@@ -0,0 +1,102 @@
+//! # Parser configuration
+//! Options to change the way the [Parser](super::Parser) reads its input.
+//! The default configuration follows the rules documented in [Parser::parse](super::Parser::parse).
+
+/// Configuration of the [Parser](super::Parser).
+/// Build it by overriding the needed fields of the default one:
+/// ```rust
+/// use chordparser::parsing::{parser_config::ParserConfig, Parser};
+///
+/// let config = ParserConfig {
+///     space_separated_group_items: true,
+///     ..Default::default()
+/// };
+/// let mut parser = Parser::with_config(config);
+/// let chord = parser.parse("C(omit3 5)").unwrap();
+/// assert_eq!(chord.note_literals, vec!["C"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParserConfig {
+    /// Inside a parenthesized omit/add group, items separated by whitespace inherit the group context as if
+    /// they were separated by commas, so `C(omit3 5)` omits both the third and the fifth.
+    /// When disabled, only commas do so and `C(omit3 5)` is read as `C(omit3)5`.
+    pub space_separated_group_items: bool,
+    /// Extensions imply the lower ones, so `C13` has a seventh, a ninth and (for minor chords) an eleventh.
+    /// When disabled extensions are taken literally, and `C13` is just a major triad with a thirteenth.
+    pub imply_extensions: bool,
+    /// An unaltered eleventh over a major chord keeps the third, so `C11` has both E and F.
+    ///
+    /// By default the third is replaced by the eleventh, so `C11` is read as `C9sus`.
+    /// The major third and the eleventh are a minor ninth apart, the harshest clash in tertian harmony,
+    /// which is why most players leave the third out of dominant eleventh chords (and why minor eleventh
+    /// chords, where the interval is a major ninth, keep their third).
+    /// Enable this to read chord symbols literally, as in classical theory where the eleventh chord stacks all six tones.
+    /// Only the notes change: the chord is named like any chord with both the third and the eleventh,
+    /// so `C11` is still normalized as `C9sus`, the same as `C7(add9,11)`.
+    pub eleventh_keeps_third: bool,
+    /// Parenthesized annotations that are not part of the chord and are ignored, like `passing` in `C7(passing)`.
+    /// They are matched ignoring case and surrounding whitespace, and only when they fill a whole group,
+    /// so `C7(passing)` is read as `C7` while `C7(b9,passing)` is still an error.
+    /// The resulting chord is parsed from the musical part only, and error positions still refer to the input as given.
+    /// Empty by default, so any annotation is an error.
+    pub ignored_annotations: Vec<String>,
+    /// Rejects sus chords that also contain a third, like `Csus4(add3)`, with [ParserError::SusWithThird](super::parser_error::ParserError::SusWithThird).
+    /// A suspension replaces the third, so some consider adding it back a contradiction.
+    /// Only written suspensions are checked: a `C11` read with [eleventh_keeps_third](Self::eleventh_keeps_third) is not an error.
+    /// Disabled by default, so `Csus4(add3)` has both E and F.
+    pub reject_sus_with_third: bool,
+    /// Notes are written in fixed-do solfège instead of letters: `Do Re Mi Fa Sol La Si` (or `Ti` for B),
+    /// so `Domaj7` is `Cmaj7` and `Sib7/Re` is `Bb7/D`. Syllables must be capitalized, and letters are no longer notes.
+    /// Since `Mi` is the note E, minor can't be written as `Mi` in this mode (`Mimi7` and `Mim7` are both `Em7`).
+    /// The resulting chord is still named with letters, so the normalized name of `Domaj7` is `CMaj7`.
+    pub solfege_notes: bool,
+    /// A major keyword (`M`, `Ma`, `Maj`, `major` and their casings) not followed by an extension implies a major seventh,
+    /// the way `△` does by default (see [triangle_implies_seventh](Self::triangle_implies_seventh)),
+    /// so `CMaj` and `CM(#11)` are read as `CMaj7` and `CMaj7(#11)`.
+    ///
+    /// By default such a keyword only makes the implied seventh of the extension after it major (`CM7`, `CMaj9`),
+    /// and alone it is a plain major triad, so `CM` and `CMaj` are both C, E, G.
+    /// An extension right after the keyword always wins, so `CM6` is a sixth chord either way.
+    pub bare_major_implies_seventh: bool,
+    /// Comma-separated items outside parentheses form a list the same way they do inside a group,
+    /// so `C,add9,11` is `C(add9,11)` and `C7,omit3,5` is `C7(omit3,5)`.
+    ///
+    /// By default commas outside a group are ignored, and each item is read on its own:
+    /// `C7,9,13` and `C7,b9,#11` read the same either way, but the 11 in `C,add9,11` is an extension
+    /// (making it `C9sus`), and the 5 in `C7,omit3,5` is a power chord, which is an error.
+    pub comma_separated_extensions: bool,
+    /// A triangle (`△`, `Δ` or `^`) implies a major seventh, so `C△` is `CMaj7` and `C△9` is `CMaj9`.
+    ///
+    /// Enabled by default. When disabled the triangle is read as a major keyword like `M`,
+    /// so `C△` is a major triad and the seventh has to be written, as in `C△7`.
+    pub triangle_implies_seventh: bool,
+    /// Words separated by whitespace can be spelled out in English, as in transcribed speech,
+    /// so `C minor seven` is `Cm7`, `F major seventh flat five` is `FMaj7(b5)` and `G over B` is `G/B`.
+    /// The recognized words, matched as whole words ignoring case, are:
+    /// - `flat`, `sharp`, `major`, `minor`, `augmented`, `diminished` and `half diminished`.
+    /// - `suspended` or `sus`, `add` or `added`, `omit` or `no`, and `over` for a slash bass.
+    /// - `two`, `three`, `four`, `five`, `six`, `seven`, `nine`, `eleven` and `thirteen`,
+    ///   and their ordinals (`second`, `third`, ..., `thirteenth`).
+    ///
+    /// Any other word is read as symbols, so both forms can be mixed (`C minor 7`).
+    /// Notes are still letters, and the resulting chord is named with symbols.
+    /// Disabled by default, so `C minor seven` is an error.
+    pub spelled_out_words: bool,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        ParserConfig {
+            space_separated_group_items: false,
+            imply_extensions: true,
+            eleventh_keeps_third: false,
+            ignored_annotations: Vec::new(),
+            reject_sus_with_third: false,
+            solfege_notes: false,
+            bare_major_implies_seventh: false,
+            comma_separated_extensions: false,
+            triangle_implies_seventh: true,
+            spelled_out_words: false,
+        }
+    }
+}
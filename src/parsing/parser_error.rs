@@ -5,6 +5,7 @@ use std::{
 };
 
 use serde::Serialize;
+use serde_json::json;
 
 /// Errors that can occur when parsing a chord.
 /// Includes a list of string messages with a reason an the position in the input string when possible.
@@ -15,7 +16,13 @@ pub enum ParserError {
     IllegalToken(usize),
     UnexpectedNote(usize),
     DuplicateModifier(String),
-    InconsistentExtension(String),
+    /// Two extensions that cannot be together, in input order.
+    InconsistentExtension {
+        first: String,
+        second: String,
+        /// The position of the second extension.
+        pos: usize,
+    },
     DuplicateExtension(usize),
     InvalidExtension(usize),
     WrongExpressionTarget(usize),
@@ -30,35 +37,26 @@ pub enum ParserError {
     MissingClosingParenthesis(usize),
     NestedParenthesis(usize),
     InvalidPowerExpression,
+    InvalidRomanNumeral(usize),
+    SusWithThird(usize),
 }
 
 impl ParserError {
     fn surround_element_at_index(&self, s: &str, index: usize) -> String {
-        let index = index - 1;
-        if index >= s.len() {
-            let mut res = s.to_string();
-            res.push_str("(_)");
-            return res;
-        }
-
-        let before = &s[..index];
-        let after = &s[index..];
-
-        format!("{} ->{}", before, after)
+        self.surround_char_at(s, index.saturating_sub(1))
     }
 
     fn surround_element_at_index_with_span(&self, s: &str, index: usize, len: usize) -> String {
-        let index = index - 1 + len;
-        if index >= s.len() {
-            let mut res = s.to_string();
-            res.push_str("(_)");
-            return res;
-        }
-
-        let before = &s[..index];
-        let after = &s[index..];
+        self.surround_char_at(s, index.saturating_sub(1).saturating_add(len))
+    }
 
-        format!("{} ->{}", before, after)
+    /// Points at the char at given 0-based index, or at the end of the string if the index is out of range.
+    /// Indexes are in chars, so multi-byte symbols like `△` are safe.
+    fn surround_char_at(&self, s: &str, index: usize) -> String {
+        match s.char_indices().nth(index) {
+            Some((byte, _)) => format!("{} ->{}", &s[..byte], &s[byte..]),
+            None => format!("{}(_)", s),
+        }
     }
 
     /// Returns the position in the input string where the error occurred.
@@ -69,8 +67,7 @@ impl ParserError {
         match self {
             ParserError::ThreeConsecutiveSemitones(_)
             | ParserError::InvalidPowerExpression
-            | ParserError::DuplicateModifier(_)
-            | ParserError::InconsistentExtension(_) => None,
+            | ParserError::DuplicateModifier(_) => None,
             ParserError::IllegalToken(pos) | ParserError::UnexpectedNote(pos) => Some(*pos),
             ParserError::DuplicateExtension(pos) | ParserError::InvalidExtension(pos) => Some(*pos),
             ParserError::UnexpectedModifier(pos) | ParserError::IllegalSlashNotation(pos) => {
@@ -79,11 +76,58 @@ impl ParserError {
             ParserError::UnexpectedClosingParenthesis(pos)
             | ParserError::NestedParenthesis(pos)
             | ParserError::WrongExpressionTarget(pos)
-            | ParserError::MissingClosingParenthesis(pos) => Some(*pos),
+            | ParserError::MissingClosingParenthesis(pos)
+            | ParserError::InvalidRomanNumeral(pos)
+            | ParserError::SusWithThird(pos)
+            | ParserError::InconsistentExtension { pos, .. } => Some(*pos),
             ParserError::MissingRootNote => Some(1),
             ParserError::IllegalAddTarget((pos, len))
             | ParserError::IllegalOrMissingOmitTarget((pos, len))
-            | ParserError::MissingAddTarget((pos, len)) => Some(*pos + *len),
+            | ParserError::MissingAddTarget((pos, len)) => Some(pos.saturating_add(*len)),
+        }
+    }
+
+    /// Returns the error with its position moved by `shift` chars, for errors found in a rewritten input.
+    /// Positions are kept at 1 at least, and errors with no position of their own are returned as they are.
+    pub(crate) fn shifted(self, shift: isize) -> ParserError {
+        let by = |pos: usize| pos.saturating_add_signed(shift).max(1);
+        match self {
+            ParserError::IllegalToken(pos) => ParserError::IllegalToken(by(pos)),
+            ParserError::UnexpectedNote(pos) => ParserError::UnexpectedNote(by(pos)),
+            ParserError::InconsistentExtension { first, second, pos } => {
+                ParserError::InconsistentExtension {
+                    first,
+                    second,
+                    pos: by(pos),
+                }
+            }
+            ParserError::DuplicateExtension(pos) => ParserError::DuplicateExtension(by(pos)),
+            ParserError::InvalidExtension(pos) => ParserError::InvalidExtension(by(pos)),
+            ParserError::WrongExpressionTarget(pos) => ParserError::WrongExpressionTarget(by(pos)),
+            ParserError::UnexpectedModifier(pos) => ParserError::UnexpectedModifier(by(pos)),
+            ParserError::MissingAddTarget((pos, len)) => {
+                ParserError::MissingAddTarget((by(pos), len))
+            }
+            ParserError::IllegalOrMissingOmitTarget((pos, len)) => {
+                ParserError::IllegalOrMissingOmitTarget((by(pos), len))
+            }
+            ParserError::IllegalAddTarget((pos, len)) => {
+                ParserError::IllegalAddTarget((by(pos), len))
+            }
+            ParserError::IllegalSlashNotation(pos) => ParserError::IllegalSlashNotation(by(pos)),
+            ParserError::UnexpectedClosingParenthesis(pos) => {
+                ParserError::UnexpectedClosingParenthesis(by(pos))
+            }
+            ParserError::MissingClosingParenthesis(pos) => {
+                ParserError::MissingClosingParenthesis(by(pos))
+            }
+            ParserError::NestedParenthesis(pos) => ParserError::NestedParenthesis(by(pos)),
+            ParserError::InvalidRomanNumeral(pos) => ParserError::InvalidRomanNumeral(by(pos)),
+            ParserError::SusWithThird(pos) => ParserError::SusWithThird(by(pos)),
+            ParserError::DuplicateModifier(_)
+            | ParserError::MissingRootNote
+            | ParserError::ThreeConsecutiveSemitones(_)
+            | ParserError::InvalidPowerExpression => self,
         }
     }
 
@@ -100,14 +144,16 @@ impl ParserError {
             | ParserError::UnexpectedClosingParenthesis(pos)
             | ParserError::MissingClosingParenthesis(pos)
             | ParserError::WrongExpressionTarget(pos)
-            | ParserError::NestedParenthesis(pos) => {
+            | ParserError::NestedParenthesis(pos)
+            | ParserError::InvalidRomanNumeral(pos)
+            | ParserError::SusWithThird(pos)
+            | ParserError::InconsistentExtension { pos, .. } => {
                 let mut res = format!("{}: ", self);
                 res.push_str(&self.surround_element_at_index(origin, *pos));
                 res
             }
             ParserError::DuplicateModifier(_)
             | ParserError::InvalidPowerExpression
-            | ParserError::InconsistentExtension(_)
             | ParserError::MissingRootNote
             | ParserError::ThreeConsecutiveSemitones(_) => {
                 format!("{}", self)
@@ -131,8 +177,12 @@ impl fmt::Display for ParserError {
             ParserError::DuplicateModifier(modifier) => {
                 write!(f, "Duplicate modifier: {}", modifier)
             }
-            ParserError::InconsistentExtension(extension) => {
-                write!(f, "Inconsistent extension: {}", extension)
+            ParserError::InconsistentExtension { first, second, pos } => {
+                write!(
+                    f,
+                    "Inconsistent extensions {} and {} at position {}",
+                    first, second, pos
+                )
             }
             ParserError::DuplicateExtension(pos) => {
                 write!(f, "Duplicate extension at position {}", pos)
@@ -151,17 +201,25 @@ impl fmt::Display for ParserError {
                 write!(f, "Three consecutive semitones: {:?}", notes)
             }
             ParserError::MissingAddTarget((pos, len)) => {
-                write!(f, "Missing add target at position {}", pos + len)
+                write!(
+                    f,
+                    "Missing add target at position {}",
+                    pos.saturating_add(*len)
+                )
             }
             ParserError::IllegalOrMissingOmitTarget((pos, len)) => {
                 write!(
                     f,
                     "Illegal or missing omit target at position {}",
-                    pos + len
+                    pos.saturating_add(*len)
                 )
             }
             ParserError::IllegalAddTarget((pos, len)) => {
-                write!(f, "Illegal add target at position {}", pos + len)
+                write!(
+                    f,
+                    "Illegal add target at position {}",
+                    pos.saturating_add(*len)
+                )
             }
             ParserError::IllegalSlashNotation(pos) => {
                 write!(f, "Illegal slash notation at position {}", pos)
@@ -178,6 +236,12 @@ impl fmt::Display for ParserError {
             ParserError::InvalidPowerExpression => {
                 write!(f, "A power chord should only contain a 5")
             }
+            ParserError::InvalidRomanNumeral(pos) => {
+                write!(f, "Invalid roman numeral at position {}", pos)
+            }
+            ParserError::SusWithThird(pos) => {
+                write!(f, "Third added to a sus chord at position {}", pos)
+            }
         }
     }
 }
@@ -193,6 +257,30 @@ impl ParserErrors {
     pub fn new(messages: Vec<ParserError>) -> ParserErrors {
         ParserErrors { errors: messages }
     }
+
+    /// Returns the errors as JSON diagnostics, meant for tooling like editors.
+    /// The result is an object with an `errors` array holding, for each error:
+    /// - `message`: the error message.
+    /// - `position`: the 1-based position of the error (see [ParserError::error_position]), or null if unknown.
+    /// - `highlight`: the error with the input pointing at the position (see [ParserError::verbose_display]).
+    /// # Arguments
+    /// * `origin` - The input the errors come from.
+    /// # Returns
+    /// * A JSON string.
+    pub fn to_json(&self, origin: &str) -> String {
+        let errors = self
+            .errors
+            .iter()
+            .map(|e| {
+                json!({
+                    "message": e.to_string(),
+                    "position": e.error_position(),
+                    "highlight": e.verbose_display(origin),
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({ "errors": errors }).to_string()
+    }
 }
 
 impl fmt::Display for ParserErrors {
@@ -202,3 +290,81 @@ impl fmt::Display for ParserErrors {
 }
 
 impl Error for ParserErrors {}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use serde_json::{json, Value};
+
+    use super::{ParserError, ParserErrors};
+
+    #[test_case("Cmaj7", 0, " ->Cmaj7")]
+    #[test_case("Cmaj7", 1, " ->Cmaj7")]
+    #[test_case("Cmaj7", 2, "C ->maj7")]
+    #[test_case("Cmaj7", 5, "Cmaj ->7")]
+    #[test_case("Cmaj7", 6, "Cmaj7(_)")]
+    #[test_case("Cmaj7", usize::MAX, "Cmaj7(_)")]
+    #[test_case("C△7(", 3, "C△ ->7(")]
+    #[test_case("C△7(", 5, "C△7((_)")]
+    #[test_case("", 0, "(_)")]
+    fn surround_element_at_index(s: &str, index: usize, expected: &str) {
+        let error = ParserError::IllegalToken(index);
+        assert_eq!(error.surround_element_at_index(s, index), expected);
+    }
+
+    #[test]
+    fn to_json() {
+        let errors = ParserErrors::new(vec![
+            ParserError::IllegalToken(3),
+            ParserError::MissingRootNote,
+            ParserError::DuplicateModifier("Sus".to_string()),
+        ]);
+        let json: Value = serde_json::from_str(&errors.to_json("Cxm")).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "errors": [
+                    {
+                        "message": "Illegal token at position 3",
+                        "position": 3,
+                        "highlight": "Illegal token at position 3: Cx ->m",
+                    },
+                    {
+                        "message": "Missing root note",
+                        "position": 1,
+                        "highlight": "Missing root note",
+                    },
+                    {
+                        "message": "Duplicate modifier: Sus",
+                        "position": null,
+                        "highlight": "Duplicate modifier: Sus",
+                    },
+                ]
+            })
+        );
+        assert_eq!(ParserErrors::new(vec![]).to_json(""), r#"{"errors":[]}"#);
+    }
+
+    #[test_case("Comit", 0, 0, " ->Comit")]
+    #[test_case("Comit", 2, 4, "Comit(_)")]
+    #[test_case("Comit", usize::MAX, usize::MAX, "Comit(_)")]
+    #[test_case("C△add", 3, 3, "C△add(_)")]
+    fn surround_element_at_index_with_span(s: &str, index: usize, len: usize, expected: &str) {
+        let error = ParserError::MissingAddTarget((index, len));
+        assert_eq!(
+            error.surround_element_at_index_with_span(s, index, len),
+            expected
+        );
+    }
+
+    #[test_case(ParserError::IllegalToken(0))]
+    #[test_case(ParserError::IllegalToken(100))]
+    #[test_case(ParserError::MissingClosingParenthesis(4))]
+    #[test_case(ParserError::MissingAddTarget((0, 0)))]
+    #[test_case(ParserError::IllegalOrMissingOmitTarget((usize::MAX, 1)))]
+    fn verbose_display_never_panics(error: ParserError) {
+        error.verbose_display("C△(");
+        error.verbose_display("");
+    }
+}
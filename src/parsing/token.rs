@@ -52,6 +52,49 @@ impl TokenType {
             _ => None,
         }
     }
+
+    /// Matches the fixed-do solfège note names, capitalized and mapped to their note letter.
+    pub fn from_solfege(i: &str) -> Option<TokenType> {
+        let letter = match i {
+            "Do" => "C",
+            "Re" => "D",
+            "Mi" => "E",
+            "Fa" => "F",
+            "Sol" => "G",
+            "La" => "A",
+            "Si" | "Ti" => "B",
+            _ => return None,
+        };
+        Some(TokenType::Note(letter.to_string()))
+    }
+    /// Matches the spelled-out words read with [ParserConfig::spelled_out_words](super::parser_config::ParserConfig::spelled_out_words),
+    /// ignoring case. `half` is the first half of `half diminished`, the lexer drops the `diminished` after it.
+    pub fn from_word(i: &str) -> Option<TokenType> {
+        let extension = match i.to_lowercase().as_str() {
+            "flat" => return Some(TokenType::Flat),
+            "sharp" => return Some(TokenType::Sharp),
+            "major" => return Some(TokenType::Maj),
+            "minor" => return Some(TokenType::Minor),
+            "augmented" => return Some(TokenType::Aug),
+            "diminished" => return Some(TokenType::Dim),
+            "half" => return Some(TokenType::HalfDim),
+            "suspended" | "sus" => return Some(TokenType::Sus),
+            "add" | "added" => return Some(TokenType::Add),
+            "omit" | "no" => return Some(TokenType::Omit),
+            "over" => return Some(TokenType::Slash),
+            "two" | "second" => "2",
+            "three" | "third" => "3",
+            "four" | "fourth" => "4",
+            "five" | "fifth" => "5",
+            "six" | "sixth" => "6",
+            "seven" | "seventh" => "7",
+            "nine" | "ninth" => "9",
+            "eleven" | "eleventh" => "11",
+            "thirteen" | "thirteenth" => "13",
+            _ => return None,
+        };
+        Some(TokenType::Extension(extension.to_string()))
+    }
 }
 
 impl Display for TokenType {
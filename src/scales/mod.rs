@@ -0,0 +1,133 @@
+//! # Scales
+//! Notes of the scales a chord can be taken from, spelled with one note for each letter.
+
+use crate::chord::{intervals::Interval, note::Note, quality::Quality, Chord};
+
+/// Semitones from the tonic of each degree of the major scale.
+static MAJOR: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+/// Semitones from the tonic of each degree of the natural minor scale.
+static MINOR: [u8; 7] = [0, 2, 3, 5, 7, 8, 10];
+
+/// Returns the notes of the major scale of given root.
+/// Every degree is spelled from its own letter, so `Gb` gives Gb, Ab, Bb, Cb, Db, Eb, F.
+/// # Arguments
+/// * `root` - The tonic of the scale.
+/// # Returns
+/// * The seven notes of the scale, starting at the root.
+pub fn major_scale(root: &Note) -> [Note; 7] {
+    scale(root, &MAJOR)
+}
+
+/// Returns the notes of the natural minor scale of given root.
+/// Every degree is spelled from its own letter, so `D#` gives D#, E#, F#, G#, A#, B, C#.
+/// # Arguments
+/// * `root` - The tonic of the scale.
+/// # Returns
+/// * The seven notes of the scale, starting at the root.
+pub fn minor_scale(root: &Note) -> [Note; 7] {
+    scale(root, &MINOR)
+}
+
+fn scale(root: &Note, semitones: &[u8; 7]) -> [Note; 7] {
+    std::array::from_fn(|degree| root.get_note(semitones[degree], degree as u8 + 1))
+}
+
+/// Chord scales as pairs of semitones from the root and the degree whose letter spells them.
+/// Scales with more or less than seven notes repeat or skip letters.
+static IONIAN: [(u8, u8); 7] = [(0, 1), (2, 2), (4, 3), (5, 4), (7, 5), (9, 6), (11, 7)];
+static DORIAN: [(u8, u8); 7] = [(0, 1), (2, 2), (3, 3), (5, 4), (7, 5), (9, 6), (10, 7)];
+static PHRYGIAN: [(u8, u8); 7] = [(0, 1), (1, 2), (3, 3), (5, 4), (7, 5), (8, 6), (10, 7)];
+static LYDIAN: [(u8, u8); 7] = [(0, 1), (2, 2), (4, 3), (6, 4), (7, 5), (9, 6), (11, 7)];
+static MIXOLYDIAN: [(u8, u8); 7] = [(0, 1), (2, 2), (4, 3), (5, 4), (7, 5), (9, 6), (10, 7)];
+static AEOLIAN: [(u8, u8); 7] = [(0, 1), (2, 2), (3, 3), (5, 4), (7, 5), (8, 6), (10, 7)];
+static LOCRIAN: [(u8, u8); 7] = [(0, 1), (1, 2), (3, 3), (5, 4), (6, 5), (8, 6), (10, 7)];
+static LOCRIAN_SHARP_2: [(u8, u8); 7] = [(0, 1), (2, 2), (3, 3), (5, 4), (6, 5), (8, 6), (10, 7)];
+static MELODIC_MINOR: [(u8, u8); 7] = [(0, 1), (2, 2), (3, 3), (5, 4), (7, 5), (9, 6), (11, 7)];
+static LYDIAN_AUGMENTED: [(u8, u8); 7] = [(0, 1), (2, 2), (4, 3), (6, 4), (8, 5), (9, 6), (11, 7)];
+static LYDIAN_DOMINANT: [(u8, u8); 7] = [(0, 1), (2, 2), (4, 3), (6, 4), (7, 5), (9, 6), (10, 7)];
+static MIXOLYDIAN_FLAT_13: [(u8, u8); 7] =
+    [(0, 1), (2, 2), (4, 3), (5, 4), (7, 5), (8, 6), (10, 7)];
+static ALTERED: [(u8, u8); 7] = [(0, 1), (1, 2), (3, 3), (4, 4), (6, 5), (8, 6), (10, 7)];
+static WHOLE_TONE: [(u8, u8); 6] = [(0, 1), (2, 2), (4, 3), (6, 4), (8, 5), (10, 7)];
+static HALF_WHOLE: [(u8, u8); 8] = [
+    (0, 1),
+    (1, 2),
+    (3, 2),
+    (4, 3),
+    (6, 4),
+    (7, 5),
+    (9, 6),
+    (10, 7),
+];
+static WHOLE_HALF: [(u8, u8); 8] = [
+    (0, 1),
+    (2, 2),
+    (3, 3),
+    (5, 4),
+    (6, 5),
+    (8, 6),
+    (9, 7),
+    (11, 7),
+];
+
+/// Picks the chord scale of a chord, see [Chord::suggested_scale] for the mapping.
+pub(crate) fn suggested_scale(chord: &Chord) -> Vec<Note> {
+    let has = |i: Interval| chord.real_intervals.contains(&i);
+    let altered_ninth = has(Interval::FlatNinth) || has(Interval::SharpNinth);
+    let altered_fifth = has(Interval::DiminishedFifth)
+        || has(Interval::AugmentedFifth)
+        || has(Interval::FlatThirteenth);
+    let template: &[(u8, u8)] = match chord.quality {
+        Quality::Dominant if altered_ninth && altered_fifth => &ALTERED,
+        Quality::Dominant if altered_ninth => &HALF_WHOLE,
+        Quality::Dominant if has(Interval::SharpEleventh) => &LYDIAN_DOMINANT,
+        Quality::Dominant if has(Interval::AugmentedFifth) => &WHOLE_TONE,
+        Quality::Dominant if has(Interval::FlatThirteenth) => &MIXOLYDIAN_FLAT_13,
+        Quality::Dominant => &MIXOLYDIAN,
+        Quality::Minor if has(Interval::DiminishedFifth) && has(Interval::Ninth) => {
+            &LOCRIAN_SHARP_2
+        }
+        Quality::Minor if has(Interval::DiminishedFifth) => &LOCRIAN,
+        Quality::Minor if has(Interval::MajorSeventh) => &MELODIC_MINOR,
+        Quality::Minor if has(Interval::FlatNinth) => &PHRYGIAN,
+        Quality::Minor if has(Interval::MinorSixth) || has(Interval::FlatThirteenth) => &AEOLIAN,
+        Quality::Minor => &DORIAN,
+        Quality::Diminished => &WHOLE_HALF,
+        Quality::Augmented => &LYDIAN_AUGMENTED,
+        _ if has(Interval::AugmentedFifth) => &LYDIAN_AUGMENTED,
+        _ if has(Interval::SharpEleventh) || has(Interval::DiminishedFifth) => &LYDIAN,
+        _ => &IONIAN,
+    };
+    template
+        .iter()
+        .map(|(st, degree)| chord.root.get_note(*st, *degree))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{major_scale, minor_scale};
+    use crate::chord::note::{Modifier, Note, NoteLiteral};
+
+    fn names(notes: [Note; 7]) -> Vec<String> {
+        notes.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test_case(Note::new(NoteLiteral::C, None), vec!["C", "D", "E", "F", "G", "A", "B"])]
+    #[test_case(Note::new(NoteLiteral::G, Some(Modifier::Flat)), vec!["Gb", "Ab", "Bb", "Cb", "Db", "Eb", "F"])]
+    #[test_case(Note::new(NoteLiteral::F, Some(Modifier::Sharp)), vec!["F#", "G#", "A#", "B", "C#", "D#", "E#"])]
+    #[test_case(Note::new(NoteLiteral::F, None), vec!["F", "G", "A", "Bb", "C", "D", "E"])]
+    fn major(root: Note, expected: Vec<&str>) {
+        assert_eq!(names(major_scale(&root)), expected);
+    }
+
+    #[test_case(Note::new(NoteLiteral::A, None), vec!["A", "B", "C", "D", "E", "F", "G"])]
+    #[test_case(Note::new(NoteLiteral::C, None), vec!["C", "D", "Eb", "F", "G", "Ab", "Bb"])]
+    #[test_case(Note::new(NoteLiteral::D, Some(Modifier::Sharp)), vec!["D#", "E#", "F#", "G#", "A#", "B", "C#"])]
+    #[test_case(Note::new(NoteLiteral::E, Some(Modifier::Flat)), vec!["Eb", "F", "Gb", "Ab", "Bb", "Cb", "Db"])]
+    fn minor(root: Note, expected: Vec<&str>) {
+        assert_eq!(names(minor_scale(&root)), expected);
+    }
+}
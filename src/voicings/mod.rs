@@ -246,3 +246,64 @@ pub fn generate_voicing(ch: &Chord, lead_note: Option<u8>) -> MidiCodesVoicing {
     res.push(lead);
     res
 }
+
+/// Creates a voicing for a chord like [generate_voicing], along with the note each MIDI code stands for.
+/// The notes are spelled as in the chord, so they can be used to label the voicing: the first code is the slash-bass
+/// when present, placed an octave down, and it is labeled with the bass note as written (`E` in `C/E`, `Fb` in `Db/Fb`).
+/// # Arguments
+/// * `ch` - The chord to generate the voicing
+/// * `lead_note` - The lead note of the voicing, see [generate_voicing].
+/// # Returns
+/// The MIDI codes of the voicing and, in the same order, the notes they stand for
+pub fn generate_voicing_with_notes(
+    ch: &Chord,
+    lead_note: Option<u8>,
+) -> (MidiCodesVoicing, Vec<Note>) {
+    let voicing = generate_voicing(ch, lead_note);
+    let notes = voicing
+        .iter()
+        .enumerate()
+        .map(|(i, code)| match &ch.bass {
+            Some(bass) if i == 0 => bass.clone(),
+            _ => ch
+                .notes
+                .iter()
+                .find(|n| n.to_semitone() == code % 12)
+                .unwrap_or(&ch.root)
+                .clone(),
+        })
+        .collect();
+    (voicing, notes)
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{generate_voicing, generate_voicing_with_notes};
+    use crate::parsing::Parser;
+
+    #[test_case("C", None)]
+    #[test_case("C/E", None)]
+    #[test_case("Db/Fb", None)]
+    #[test_case("G13(b9)/B", Some(74))]
+    #[test_case("Cb7/Bbb", Some(60))]
+    #[test_case("F#m7b5", Some(70))]
+    #[test_case("C#/E#", Some(64))]
+    fn voicing_with_notes(input: &str, lead: Option<u8>) {
+        let mut parser = Parser::new();
+        let chord = parser.parse(input).unwrap();
+        let (voicing, notes) = generate_voicing_with_notes(&chord, lead);
+        assert_eq!(voicing, generate_voicing(&chord, lead));
+        assert_eq!(voicing.len(), notes.len());
+        for (code, note) in voicing.iter().zip(&notes) {
+            assert_eq!(code % 12, note.to_semitone());
+            assert!(chord.notes.contains(note) || chord.bass.as_ref() == Some(note));
+        }
+        if let Some(bass) = &chord.bass {
+            assert_eq!(&notes[0], bass);
+            assert_eq!(voicing[0] + 12, bass.to_midi_code());
+            assert_eq!(notes[1], chord.root);
+        }
+    }
+}
@@ -0,0 +1,645 @@
+use chordparser::{
+    chord::{
+        intervals::Interval,
+        note::{Modifier, Note, NoteLiteral, NoteRole},
+        Chord, ChordDiff,
+    },
+    parsing::Parser,
+};
+use test_case::test_case;
+
+/// This test suite covers the queries used to analyze an already parsed chord.
+
+#[test_case("C", Some(vec!["C", "E", "G"]))]
+#[test_case("Cmi7", Some(vec!["C", "Eb", "G", "Bb"]))]
+#[test_case("C6", Some(vec!["C", "E", "G", "A"]))]
+#[test_case("Cadd2", Some(vec!["C", "E", "G", "D"]))]
+#[test_case("C13", Some(vec!["C", "E", "G", "Bb", "D", "A"]))]
+#[test_case("C7(b5,#5,#9)", Some(vec!["C", "E", "Gb", "G#", "Bb", "D#"]))]
+#[test_case("Cmi69", Some(vec!["C", "Eb", "G", "D", "A"]))]
+#[test_case("Csus", None)]
+#[test_case("C7sus2", None)]
+#[test_case("C5", None)]
+#[test_case("Cmaj7omit3", None)]
+fn test_stacked_thirds(input: &str, expected: Option<Vec<&str>>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let stacked = chord
+        .stacked_thirds()
+        .map(|notes| notes.iter().map(|n| n.to_string()).collect::<Vec<String>>());
+    assert_eq!(
+        stacked,
+        expected.map(|e| e.iter().map(|n| n.to_string()).collect())
+    );
+}
+
+#[test_case("C", Note::new(NoteLiteral::C, None), "1")]
+#[test_case("Dm7", Note::new(NoteLiteral::C, None), "2-7")]
+#[test_case("G7", Note::new(NoteLiteral::C, None), "5⁷"; "dominant seventh")]
+#[test_case("FMaj7", Note::new(NoteLiteral::C, None), "4△7")]
+#[test_case("Bdim", Note::new(NoteLiteral::C, None), "7°")]
+#[test_case("Bm7b5", Note::new(NoteLiteral::C, None), "7-7(b5)")]
+#[test_case("C/E", Note::new(NoteLiteral::C, None), "1/3")]
+#[test_case("Db7", Note::new(NoteLiteral::C, None), "b2⁷"; "flat degree dominant seventh")]
+#[test_case("C#m", Note::new(NoteLiteral::C, None), "#1-")]
+#[test_case("CmMaj7", Note::new(NoteLiteral::C, None), "1-△7")]
+#[test_case("Cdim7", Note::new(NoteLiteral::C, None), "1°7")]
+#[test_case("C69", Note::new(NoteLiteral::C, None), "1⁶⁹"; "six nine")]
+#[test_case("G13(b9)", Note::new(NoteLiteral::C, None), "5¹³(b9)"; "altered thirteenth")]
+#[test_case("Bb", Note::new(NoteLiteral::C, None), "b7")]
+#[test_case("Ab", Note::new(NoteLiteral::E, Some(Modifier::Flat)), "4")]
+#[test_case("Cm7/Bb", Note::new(NoteLiteral::E, Some(Modifier::Flat)), "6-7/5")]
+#[test_case("E", Note::new(NoteLiteral::F, Some(Modifier::Sharp)), "b7")]
+fn test_to_nashville(input: &str, key: Note, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.to_nashville(&key), expected);
+}
+
+#[test_case("C", Note::new(NoteLiteral::E, None), true)]
+#[test_case("C", Note::new(NoteLiteral::F, None), false)]
+#[test_case("C7(b9)", Note::new(NoteLiteral::C, Some(Modifier::Sharp)), true)]
+#[test_case("C/Bb", Note::new(NoteLiteral::A, Some(Modifier::Sharp)), true)]
+#[test_case("C/Bb", Note::new(NoteLiteral::B, None), false)]
+#[test_case("Cbmin", Note::new(NoteLiteral::D, None), true)]
+#[test_case("Cbmin", Note::new(NoteLiteral::E, Some(Modifier::DFlat)), true)]
+#[test_case("Cbmin", Note::new(NoteLiteral::E, None), false)]
+fn test_contains_note(input: &str, note: Note, expected: bool) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.contains_note(&note), expected);
+}
+
+#[test]
+fn test_chord_as_map_key() {
+    use std::collections::HashSet;
+
+    let mut parser = Parser::new();
+    let mut set = HashSet::new();
+    for input in ["Cmaj7", "Cmaj7", "CMaj7", "Dm7", "C/E"] {
+        set.insert(parser.parse(input).unwrap());
+    }
+    // Chords are equal (and hash equally) only when every field matches, origin included.
+    assert_eq!(set.len(), 4);
+    assert!(set.contains(&parser.parse("Dm7").unwrap()));
+}
+
+#[test_case("C#m7", Note::new(NoteLiteral::C, None), "CMaj7")]
+#[test_case("Dm7", Note::new(NoteLiteral::C, None), "Dmin7")]
+#[test_case("D7", Note::new(NoteLiteral::C, None), "Dmin7")]
+#[test_case("Bb7", Note::new(NoteLiteral::C, None), "Dmin7")]
+#[test_case("Bm7", Note::new(NoteLiteral::C, None), "Bmin7(b5)")]
+#[test_case("E", Note::new(NoteLiteral::C, None), "Emin")]
+#[test_case("Fm", Note::new(NoteLiteral::C, None), "F")]
+#[test_case("C/E", Note::new(NoteLiteral::C, None), "C")]
+#[test_case("Db", Note::new(NoteLiteral::E, Some(Modifier::Flat)), "Fmin")]
+#[test_case("F#", Note::new(NoteLiteral::G, None), "F#dim")]
+fn test_snap_to_key(input: &str, key: Note, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.snap_to_key(&key).normalized, expected);
+}
+
+#[test_case("C", vec!["C", "E", "G"])]
+#[test_case("C6", vec!["C", "E", "G", "A"])]
+#[test_case("Cadd9", vec!["C", "E", "G", "D"])]
+#[test_case("Csus2", vec!["C", "G", "D"])]
+#[test_case("C7sus", vec!["C", "F", "G", "Bb"])]
+#[test_case("C/E", vec!["E", "C", "E", "G"])]
+#[test_case("C7(b9,#11)", vec!["C", "E", "G", "Bb", "Db", "F#"])]
+#[test_case("C69/A", vec!["A", "C", "E", "G", "A", "D"])]
+fn test_notes_ascending(input: &str, expected: Vec<&str>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let notes = chord
+        .notes_ascending()
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(notes, expected);
+}
+
+#[test_case("Cmaj7add3", vec![Interval::MajorThird])]
+#[test_case("C13add9", vec![Interval::Ninth])]
+#[test_case("C9(add9,add13)", vec![Interval::Ninth])]
+#[test_case("Cadd9", vec![])]
+#[test_case("C7add13", vec![])]
+#[test_case("Cmaj7", vec![])]
+fn test_redundant_adds(input: &str, expected: Vec<Interval>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.redundant_adds(), expected);
+}
+
+#[test_case("C", "Am", true)]
+#[test_case("CMaj7", "Am7", true)]
+#[test_case("Eb6", "Cm", true)]
+#[test_case("G7", "Db7", true)]
+#[test_case("G7(b9)", "C#7", true)]
+#[test_case("G7", "Dm7", true)]
+#[test_case("G9", "Dm", true)]
+#[test_case("C", "Em", false)]
+#[test_case("Am", "Cm", false)]
+#[test_case("G7", "D7", false)]
+#[test_case("G7", "Am7", false)]
+#[test_case("G7", "G7", false)]
+fn test_is_substitute_for(a: &str, b: &str, expected: bool) {
+    let mut parser = Parser::new();
+    let a = parser.parse(a).unwrap();
+    let b = parser.parse(b).unwrap();
+    assert_eq!(a.is_substitute_for(&b), expected);
+    assert_eq!(b.is_substitute_for(&a), expected);
+}
+
+#[test_case("G7", Note::new(NoteLiteral::C, None), true)]
+#[test_case("G13(b9)", Note::new(NoteLiteral::C, None), true)]
+#[test_case("G7sus", Note::new(NoteLiteral::C, None), true)]
+#[test_case("G7/B", Note::new(NoteLiteral::C, None), true)]
+#[test_case("D7", Note::new(NoteLiteral::G, None), true)]
+#[test_case("Ab7", Note::new(NoteLiteral::C, Some(Modifier::Sharp)), true)]
+#[test_case("E7alt", Note::new(NoteLiteral::A, None), true)]
+#[test_case("G", Note::new(NoteLiteral::C, None), false)]
+#[test_case("GMaj7", Note::new(NoteLiteral::C, None), false)]
+#[test_case("Gm7", Note::new(NoteLiteral::C, None), false)]
+#[test_case("G7", Note::new(NoteLiteral::D, None), false)]
+#[test_case("C7", Note::new(NoteLiteral::G, None), false)]
+fn test_resolves_to(input: &str, tonic: Note, expected: bool) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.resolves_to(&tonic), expected);
+}
+
+#[test_case("C", None)]
+#[test_case("C6", None)]
+#[test_case("Cm6", None)]
+#[test_case("Cadd9", None)]
+#[test_case("C7", Some(Interval::MinorSeventh))]
+#[test_case("C13", Some(Interval::MinorSeventh))]
+#[test_case("Cm7b5", Some(Interval::MinorSeventh))]
+#[test_case("CMaj7", Some(Interval::MajorSeventh))]
+#[test_case("CmMaj9", Some(Interval::MajorSeventh))]
+#[test_case("C6Maj7", Some(Interval::MajorSeventh))]
+#[test_case("Cdim7", Some(Interval::DiminishedSeventh))]
+#[test_case("Cdim7Maj7", Some(Interval::DiminishedSeventh))]
+fn test_seventh(input: &str, expected: Option<Interval>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.seventh(), expected);
+}
+
+#[test_case("C", Some(36))]
+#[test_case("Cm7", Some(36))]
+#[test_case("C/E", Some(40))]
+#[test_case("Bb7/Ab", Some(44))]
+#[test_case("F#", Some(42))]
+fn test_bass_midi_code(input: &str, expected: Option<u8>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.bass_midi_code(), expected);
+    assert_eq!(
+        chord.bass_midi_code(),
+        chord.to_midi_codes().first().copied()
+    );
+}
+
+#[test]
+fn test_to_table() {
+    let mut parser = Parser::new();
+    let chord = parser.parse("C").unwrap();
+    let expected = "\
+note | interval | semitone | degree
+-----+----------+----------+-------
+C    | 1        | 0        | 1
+E    | 3        | 4        | 3
+G    | 5        | 7        | 5";
+    assert_eq!(chord.to_table(), expected);
+
+    let chord = parser.parse("Bbm7(b9)").unwrap();
+    let table = chord.to_table();
+    assert_eq!(table.lines().count(), chord.notes.len() + 2);
+    assert!(table.lines().any(|l| l == "Cb   | b9       | 13       | 9"));
+    assert!(table.lines().any(|l| l == "Ab   | 7        | 10       | 7"));
+}
+
+#[test_case("C", 7, true)]
+#[test_case("C5", 7, true)]
+#[test_case("Cmaj7", 11, true)]
+#[test_case("Cdim7", 9, true)]
+#[test_case("C9", 14, false)]
+#[test_case("C13", 21, false)]
+#[test_case("C/G", 12, true)]
+#[test_case("C/E", 15, false)]
+#[test_case("C/C", 19, false)]
+#[test_case("C/B", 8, true)]
+fn test_span_semitones(input: &str, span: u8, fits: bool) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.span_semitones(), span);
+    assert_eq!(chord.fits_in_octave(), fits);
+}
+
+#[test_case("C", vec![])]
+#[test_case("C13", vec![])]
+#[test_case("C/E", vec![Interval::MajorThird])]
+#[test_case("C/C", vec![Interval::Unison])]
+#[test_case("Am7/G", vec![Interval::MinorSeventh])]
+#[test_case("C/Bb", vec![])]
+#[test_case("Dm/F", vec![Interval::MinorThird])]
+fn test_doubled_degrees(input: &str, expected: Vec<Interval>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.has_doublings(), !expected.is_empty());
+    assert_eq!(chord.doubled_degrees(), expected);
+}
+
+#[test_case("C", vec![])]
+#[test_case("Cm7b5", vec![])]
+#[test_case("C9", vec!["D"])]
+#[test_case("C7(b9,#11,b13)", vec!["Db", "F#", "Ab"])]
+#[test_case("F#13", vec!["G#", "D#"])]
+#[test_case("Ebm11/Db", vec!["F", "Ab"])]
+#[test_case("C6/9", vec!["D"])]
+fn test_color_tones(input: &str, expected: Vec<&str>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let tones = chord.color_tones();
+    assert_eq!(
+        tones.iter().map(|n| n.to_string()).collect::<Vec<_>>(),
+        expected
+    );
+    assert_eq!(tones.len(), chord.tensions().len());
+}
+
+#[test_case("C#7/G#", "Db7/Ab", true)]
+#[test_case("C#7", "Db7", true)]
+#[test_case("Cm7b5", "B#ø", true)]
+#[test_case("Cmaj7", "C△", true)]
+#[test_case("C", "C/C", false)]
+#[test_case("C6", "Am7/C", false)]
+#[test_case("C7", "C7/E", false)]
+#[test_case("C7", "C9", false)]
+fn test_fingerprint(a: &str, b: &str, equal: bool) {
+    let mut parser = Parser::new();
+    let a = parser.parse(a).unwrap();
+    let b = parser.parse(b).unwrap();
+    assert_eq!(a.fingerprint() == b.fingerprint(), equal);
+    assert_eq!(a.fingerprint() >> 31, 0);
+    assert_eq!(
+        a.fingerprint(),
+        a.transpose_to_octave(&a.root, 6).fingerprint()
+    );
+}
+
+#[test_case("C", false, 0b0000_1001_0001)]
+#[test_case("C7", false, 0b0100_1001_0001)]
+#[test_case("Am7/C", false, 0b0010_1001_0001)]
+#[test_case("C6", true, 0b0010_1001_0001)]
+#[test_case("C/Bb", false, 0b0000_1001_0001)]
+#[test_case("C/Bb", true, 0b0100_1001_0001)]
+#[test_case("B7(b9)", false, 0b1010_0100_1001)]
+fn test_pitch_mask(input: &str, include_bass: bool, expected: u16) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.pitch_mask(include_bass), expected);
+    assert!(chord.pitch_mask(include_bass) < 1 << 12);
+}
+
+#[test_case("Cmaj7", "C", NoteRole::ChordTone)]
+#[test_case("Cmaj7", "B", NoteRole::ChordTone)]
+#[test_case("Cmaj7", "D", NoteRole::Tension)]
+#[test_case("Cmaj7", "F", NoteRole::Avoid)]
+#[test_case("Cmaj7", "A", NoteRole::Tension)]
+#[test_case("Cmaj7", "Eb", NoteRole::Outside)]
+#[test_case("C7", "F", NoteRole::Avoid)]
+#[test_case("C7", "B", NoteRole::Outside)]
+#[test_case("C7(b9)", "Db", NoteRole::Tension)]
+#[test_case("C7(b9)", "C#", NoteRole::Tension)]
+#[test_case("C9", "D", NoteRole::Tension)]
+#[test_case("Cm7", "F", NoteRole::Tension)]
+#[test_case("Cm7b5", "Db", NoteRole::Avoid)]
+#[test_case("Cdim7", "D", NoteRole::Tension)]
+#[test_case("C/Bb", "Bb", NoteRole::ChordTone)]
+#[test_case("CMaj7(#11)", "F#", NoteRole::Tension)]
+fn test_classify_note(input: &str, note: &str, expected: NoteRole) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let note = parser.parse(note).unwrap().root;
+    assert_eq!(chord.classify_note(&note), expected);
+}
+
+#[test_case("C7(9,#11,13)", Some(("D", "C7")))]
+#[test_case("C13(#11)", Some(("D", "C7")))]
+#[test_case("C7(b9,13)", Some(("A", "C7")))]
+#[test_case("C7(b9,#11)", Some(("F#", "C7")))]
+#[test_case("C7(#9,b13)", Some(("Ab", "C7(omit5)")))]
+#[test_case("G7(b9,b13)", Some(("Abmin", "G7(omit5)")))]
+#[test_case("C13(#11)/E", Some(("D", "C7/E")))]
+#[test_case("C13", None)]
+#[test_case("C7(b9)", None)]
+#[test_case("C7alt", None)]
+#[test_case("Cmaj7(9,#11,13)", None)]
+fn test_as_polychord(input: &str, expected: Option<(&str, &str)>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let res = chord.as_polychord();
+    assert_eq!(
+        res.as_ref().map(|(u, l)| (u.as_str(), l.as_str())),
+        expected
+    );
+}
+
+#[test_case("C", vec![(1, 1), (5, 4), (3, 2)])]
+#[test_case("Cm7", vec![(1, 1), (6, 5), (3, 2), (16, 9)])]
+#[test_case("Cmaj9", vec![(1, 1), (5, 4), (3, 2), (15, 8), (9, 4)])]
+#[test_case("Cdim7", vec![(1, 1), (6, 5), (64, 45), (128, 75)])]
+#[test_case("C/E", vec![(1, 1), (5, 4), (3, 2)])]
+fn test_just_ratios(input: &str, expected: Vec<(u32, u32)>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.just_ratios(), expected);
+}
+
+#[test_case("C", "C", "G")]
+#[test_case("Cmaj7", "C", "B")]
+#[test_case("C13", "C", "A")]
+#[test_case("C7(b9)", "C", "Db")]
+#[test_case("Am7/G", "G", "G")]
+#[test_case("C/E", "E", "G")]
+#[test_case("Bb/Ab", "Ab", "F")]
+#[test_case("CBass", "C", "C")]
+fn test_lowest_and_highest_note(input: &str, lowest: &str, highest: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.lowest_note().to_string(), lowest);
+    assert_eq!(chord.highest_note().to_string(), highest);
+}
+
+#[test_case("C", vec![])]
+#[test_case("Cmaj7", vec![])]
+#[test_case("C6", vec![])]
+#[test_case("C7sus4", vec![])]
+#[test_case("C9", vec![Interval::Ninth])]
+#[test_case("C13", vec![Interval::Ninth, Interval::Thirteenth])]
+#[test_case("Cm11", vec![Interval::Ninth, Interval::Eleventh])]
+#[test_case("C7(b9,#11,b13)", vec![Interval::FlatNinth, Interval::SharpEleventh, Interval::FlatThirteenth])]
+#[test_case("Cm6/9", vec![Interval::Ninth])]
+#[test_case("C(add9)/E", vec![Interval::Ninth])]
+fn test_tensions(input: &str, expected: Vec<Interval>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.tensions(), expected);
+    assert_eq!(chord.is_extended(), !expected.is_empty());
+}
+
+#[test_case("C", "Major")]
+#[test_case("C6", "Major 6th")]
+#[test_case("Cmaj9", "Major 7th")]
+#[test_case("Cm", "Minor")]
+#[test_case("Cm6", "Minor 6th")]
+#[test_case("Cm11", "Minor 7th")]
+#[test_case("CmMaj7", "Minor/Major 7th")]
+#[test_case("C13", "Dominant 7th")]
+#[test_case("C7sus4", "Dominant 7th")]
+#[test_case("Cm7b5", "Half-diminished")]
+#[test_case("Cø", "Half-diminished")]
+#[test_case("Cdim", "Diminished")]
+#[test_case("Cdim7", "Diminished 7th")]
+#[test_case("C+", "Augmented")]
+#[test_case("C5", "Power chord")]
+fn test_quality_name(input: &str, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.quality_name(), expected);
+}
+
+#[test_case("C", "Am", false, vec!["C", "E"])]
+#[test_case("Am", "C", false, vec!["C", "E"])]
+#[test_case("G7", "Cmaj7", false, vec!["G", "B"])]
+#[test_case("E", "Ab", false, vec!["G#"])]
+#[test_case("C", "F#", false, vec![])]
+#[test_case("C/Bb", "D", false, vec![])]
+#[test_case("C/Bb", "Gm", true, vec!["G", "Bb"])]
+#[test_case("C", "D/C", true, vec!["C"])]
+#[test_case("C/E", "Em", true, vec!["E", "G"])]
+fn test_common_tones(a: &str, b: &str, include_bass: bool, expected: Vec<&str>) {
+    let mut parser = Parser::new();
+    let a = parser.parse(a).unwrap();
+    let b = parser.parse(b).unwrap();
+    let common = a
+        .common_tones(&b, include_bass)
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(common, expected);
+}
+
+#[test_case("C", vec!["C", "E"])]
+#[test_case("Cm", vec!["C", "Eb"])]
+#[test_case("C7", vec!["C", "E", "Bb"])]
+#[test_case("Cmaj9", vec!["C", "E", "B"])]
+#[test_case("Cm7b5", vec!["C", "Eb", "Bb"])]
+#[test_case("Cdim7", vec!["C", "Eb", "B𝄫"])]
+#[test_case("CmMaj7", vec!["C", "Eb", "B"])]
+#[test_case("C13(#9)", vec!["C", "E", "Bb"])]
+#[test_case("C7sus", vec!["C", "F", "Bb"])]
+#[test_case("C11", vec!["C", "F", "Bb"])]
+#[test_case("C7sus2", vec!["C", "D", "Bb"])]
+#[test_case("C6", vec!["C", "E"])]
+#[test_case("C5", vec!["C"])]
+#[test_case("Cm7/Bb", vec!["C", "Eb", "Bb"])]
+fn test_shell(input: &str, expected: Vec<&str>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let shell = chord
+        .shell()
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(shell, expected);
+}
+
+#[test_case("C", None)]
+#[test_case("C/E", Some(Interval::MinorSixth))]
+#[test_case("C/G", Some(Interval::PerfectFourth))]
+#[test_case("C7/Bb", Some(Interval::MajorSecond))]
+#[test_case("Am/C", Some(Interval::MajorSixth))]
+#[test_case("C/D", Some(Interval::MinorSeventh))]
+#[test_case("C/F#", Some(Interval::DiminishedFifth))]
+#[test_case("C/Gb", Some(Interval::AugmentedFourth))]
+#[test_case("C/Ab", Some(Interval::MajorThird))]
+#[test_case("G#/C", Some(Interval::AugmentedFifth))]
+#[test_case("Bb/C#", Some(Interval::DiminishedSeventh))]
+#[test_case("C#/C", Some(Interval::MinorSecond))]
+#[test_case("C/C", Some(Interval::Unison))]
+fn test_bass_interval(input: &str, expected: Option<Interval>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.bass_interval(), expected);
+}
+
+#[test_case("C", true, false, false; "major triad")]
+#[test_case("Cm", true, false, false)]
+#[test_case("Cdim", true, false, false)]
+#[test_case("C+", true, false, false; "augmented triad")]
+#[test_case("Csus4", true, false, false)]
+#[test_case("C(b5)", true, false, false)]
+#[test_case("C/E", true, false, false)]
+#[test_case("C5", false, false, false)]
+#[test_case("C6", false, false, false)]
+#[test_case("C(omit5)", false, false, false)]
+#[test_case("C7", false, true, false)]
+#[test_case("CMaj7", false, true, false)]
+#[test_case("Cm7b5", false, true, false)]
+#[test_case("Cdim7", false, true, false)]
+#[test_case("C7sus4", false, true, false)]
+#[test_case("C7(omit5)", false, true, false)]
+#[test_case("C9", false, false, true)]
+#[test_case("C7(b9)", false, false, true)]
+#[test_case("Cm11", false, false, true)]
+#[test_case("C13", false, false, true)]
+#[test_case("C6/9", false, false, true)]
+#[test_case("Cadd9", false, false, true)]
+#[test_case("Csus2", false, false, true)]
+fn test_structure(input: &str, triad: bool, seventh: bool, extended: bool) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.is_triad(), triad);
+    assert_eq!(chord.is_seventh_chord(), seventh);
+    assert_eq!(chord.is_extended(), extended);
+}
+
+#[test_case("C7", "Cm7", vec![Interval::Unison, Interval::PerfectFifth, Interval::MinorSeventh])]
+#[test_case("C7(b9,#9)", "C7alt", vec![Interval::Unison, Interval::MajorThird, Interval::MinorSeventh, Interval::FlatNinth, Interval::SharpNinth])]
+#[test_case("C", "Cm", vec![Interval::Unison, Interval::PerfectFifth])]
+#[test_case("C/E", "Csus4", vec![Interval::Unison, Interval::PerfectFifth])]
+fn test_interval_set(a: &str, b: &str, common: Vec<Interval>) {
+    let mut parser = Parser::new();
+    let a = parser.parse(a).unwrap().interval_set();
+    let chord = parser.parse(b).unwrap();
+    let b = chord.interval_set();
+    assert_eq!(b.iter().collect::<Vec<_>>(), chord.real_intervals);
+    assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), common);
+}
+
+#[test_case("C", vec!["C", "D", "E", "F", "G", "A", "B"]; "major triad")]
+#[test_case("C5", vec!["C", "D", "E", "F", "G", "A", "B"])]
+#[test_case("FMaj7(#11)", vec!["F", "G", "A", "B", "C", "D", "E"])]
+#[test_case("EbMaj7#5", vec!["Eb", "F", "G", "A", "B", "C", "D"])]
+#[test_case("G7", vec!["G", "A", "B", "C", "D", "E", "F"])]
+#[test_case("G7sus/F", vec!["G", "A", "B", "C", "D", "E", "F"])]
+#[test_case("Bb9(#11)", vec!["Bb", "C", "D", "E", "F", "G", "Ab"])]
+#[test_case("G7alt", vec!["G", "Ab", "Bb", "Cb", "Db", "Eb", "F"])]
+#[test_case("G7(b9)", vec!["G", "Ab", "A#", "B", "C#", "D", "E", "F"])]
+#[test_case("G7(#5)", vec!["G", "A", "B", "C#", "D#", "F"])]
+#[test_case("G7(b13)", vec!["G", "A", "B", "C", "D", "Eb", "F"])]
+#[test_case("Dm7", vec!["D", "E", "F", "G", "A", "B", "C"])]
+#[test_case("Am(b6)", vec!["A", "B", "C", "D", "E", "F", "G"])]
+#[test_case("Em7(b9)", vec!["E", "F", "G", "A", "B", "C", "D"])]
+#[test_case("CmMaj7", vec!["C", "D", "Eb", "F", "G", "A", "B"])]
+#[test_case("Bm7b5", vec!["B", "C", "D", "E", "F", "G", "A"])]
+#[test_case("Am9(b5)", vec!["A", "B", "C", "D", "Eb", "F", "G"])]
+#[test_case("Cdim7", vec!["C", "D", "Eb", "F", "Gb", "Ab", "B𝄫", "B"])]
+#[test_case("C+", vec!["C", "D", "E", "F#", "G#", "A", "B"]; "augmented triad")]
+fn test_suggested_scale(input: &str, expected: Vec<&str>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let scale = chord.suggested_scale();
+    assert_eq!(
+        scale.iter().map(|n| n.to_string()).collect::<Vec<_>>(),
+        expected
+    );
+    for note in &chord.notes {
+        assert!(
+            scale.iter().any(|n| n.to_semitone() == note.to_semitone()),
+            "{}",
+            note
+        );
+    }
+}
+
+#[test_case(
+    Note::new(NoteLiteral::E, None),
+    "7/3",
+    Some(Note::new(NoteLiteral::C, None))
+)]
+#[test_case(
+    Note::new(NoteLiteral::B, Some(Modifier::Flat)),
+    "m7/5",
+    Some(Note::new(NoteLiteral::E, Some(Modifier::Flat)))
+)]
+#[test_case(
+    Note::new(NoteLiteral::E, Some(Modifier::Flat)),
+    "m/3",
+    Some(Note::new(NoteLiteral::C, None))
+)]
+#[test_case(
+    Note::new(NoteLiteral::B, None),
+    "maj7/7",
+    Some(Note::new(NoteLiteral::C, None))
+)]
+#[test_case(
+    Note::new(NoteLiteral::F, None),
+    "7/7",
+    Some(Note::new(NoteLiteral::G, None))
+)]
+#[test_case(
+    Note::new(NoteLiteral::D, None),
+    "6/9/9",
+    Some(Note::new(NoteLiteral::C, None))
+)]
+#[test_case(
+    Note::new(NoteLiteral::G, None),
+    "sus4/4",
+    Some(Note::new(NoteLiteral::D, None))
+)]
+#[test_case(
+    Note::new(NoteLiteral::C, None),
+    "7/1",
+    Some(Note::new(NoteLiteral::C, None))
+)]
+#[test_case(Note::new(NoteLiteral::E, None), "7", None)]
+#[test_case(Note::new(NoteLiteral::D, None), "6/9", None)]
+#[test_case(Note::new(NoteLiteral::D, None), "7/9", None)]
+#[test_case(Note::new(NoteLiteral::G, None), "7(b5,#5)/5", None)]
+#[test_case(Note::new(NoteLiteral::D, None), "7(b9,#9)/9", None)]
+#[test_case(Note::new(NoteLiteral::E, None), "m7/G/3", None)]
+#[test_case(Note::new(NoteLiteral::E, None), "x/3", None)]
+fn test_root_from_bass(bass: Note, descriptor: &str, expected: Option<Note>) {
+    let root = Chord::root_from_bass(&bass, descriptor);
+    assert_eq!(root, expected);
+    if let Some(root) = root {
+        let quality = descriptor.rsplit_once('/').unwrap().0;
+        let chord = Parser::new().parse_descriptor(root, quality).unwrap();
+        assert!(chord.notes.contains(&bass));
+    }
+}
+
+#[test_case("C7", "C7", ChordDiff::default())]
+#[test_case("C7", "C9", ChordDiff { added: vec![Interval::Ninth], ..Default::default() })]
+#[test_case("C", "Cm", ChordDiff {
+    added: vec![Interval::MinorThird],
+    removed: vec![Interval::MajorThird],
+    quality_changed: true,
+    ..Default::default()
+})]
+#[test_case("C7", "D7", ChordDiff { root_changed: true, ..Default::default() })]
+#[test_case("C#7", "Db7", ChordDiff { root_changed: true, ..Default::default() })]
+#[test_case("C", "C/E", ChordDiff { bass_changed: true, ..Default::default() })]
+#[test_case("Cmaj7", "G7/B", ChordDiff {
+    added: vec![Interval::MinorSeventh],
+    removed: vec![Interval::MajorSeventh],
+    root_changed: true,
+    bass_changed: true,
+    quality_changed: true,
+})]
+#[test_case("C7", "C7(#9)", ChordDiff { added: vec![Interval::SharpNinth], ..Default::default() })]
+fn test_diff(from: &str, to: &str, expected: ChordDiff) {
+    let mut parser = Parser::new();
+    let from = parser.parse(from).unwrap();
+    let to = parser.parse(to).unwrap();
+    let diff = from.diff(&to);
+    assert_eq!(diff, expected);
+    let back = to.diff(&from);
+    assert_eq!(back.added, diff.removed);
+    assert_eq!(back.removed, diff.added);
+}
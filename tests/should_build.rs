@@ -0,0 +1,146 @@
+use chordparser::{
+    chord::{
+        intervals::Interval,
+        note::{Modifier, Note, NoteLiteral},
+        presets::{self, chord_names_containing},
+        Chord,
+    },
+    parsing::Parser,
+};
+use test_case::test_case;
+
+/// This test suite covers chords built from intervals instead of parsed from a string.
+
+#[test_case(presets::POWER, "C5")]
+#[test_case(presets::MAJOR_TRIAD, "C")]
+#[test_case(presets::MINOR_TRIAD, "Cm")]
+#[test_case(presets::DIMINISHED_TRIAD, "Cdim")]
+#[test_case(presets::AUGMENTED_TRIAD, "C+")]
+#[test_case(presets::SUS2, "Csus2")]
+#[test_case(presets::SUS4, "Csus4")]
+#[test_case(presets::MAJOR_6, "C6")]
+#[test_case(presets::MINOR_6, "Cm6")]
+#[test_case(presets::SIX_NINE, "C69")]
+#[test_case(presets::ADD_9, "Cadd9")]
+#[test_case(presets::MAJOR_7, "CMaj7")]
+#[test_case(presets::DOMINANT_7, "C7")]
+#[test_case(presets::DOMINANT_7_SUS4, "C7sus4")]
+#[test_case(presets::MINOR_7, "Cm7")]
+#[test_case(presets::MINOR_MAJOR_7, "CmMaj7")]
+#[test_case(presets::MINOR_7B5, "Cm7b5")]
+#[test_case(presets::DIMINISHED_7, "Cdim7")]
+#[test_case(presets::AUGMENTED_7, "C7#5")]
+#[test_case(presets::MAJOR_9, "CMaj9")]
+#[test_case(presets::DOMINANT_9, "C9")]
+#[test_case(presets::MINOR_9, "Cm9")]
+#[test_case(presets::DOMINANT_11, "C11")]
+#[test_case(presets::MINOR_11, "Cm11")]
+#[test_case(presets::MAJOR_13, "CMaj13")]
+#[test_case(presets::DOMINANT_13, "C13")]
+fn test_presets(formula: &[Interval], name: &str) {
+    let mut parser = Parser::new();
+    let parsed = parser.parse(name).unwrap();
+    let built = Chord::from_root_and_intervals(Note::new(NoteLiteral::C, None), formula);
+    assert_eq!(built.normalized, parsed.normalized);
+    assert_eq!(built.origin, parsed.normalized);
+    assert_eq!(built.real_intervals, parsed.real_intervals);
+    assert_eq!(built.note_literals, parsed.note_literals);
+    assert_eq!(built.quality, parsed.quality);
+}
+
+#[test]
+fn test_from_root_and_intervals() {
+    let root = Note::new(NoteLiteral::E, Some(Modifier::Flat));
+    let chord = Chord::from_root_and_intervals(
+        root,
+        &[
+            Interval::MinorSeventh,
+            Interval::MajorThird,
+            Interval::MajorThird,
+        ],
+    );
+    assert_eq!(chord.note_literals, vec!["Eb", "G", "Db"]);
+    assert_eq!(chord.normalized, "Eb7(omit5)");
+    assert_eq!(chord.descriptor, "7(omit5)");
+}
+
+#[test_case(Note::new(NoteLiteral::C, None), vec![0, 4, 7], "C")]
+#[test_case(Note::new(NoteLiteral::C, None), vec![4, 7], "C")]
+#[test_case(Note::new(NoteLiteral::C, None), vec![0, 4, 7, 9], "C6")]
+#[test_case(Note::new(NoteLiteral::A, None), vec![0, 4, 7, 9], "Amin7")]
+#[test_case(Note::new(NoteLiteral::G, None), vec![7, 11, 2, 5], "G7")]
+#[test_case(Note::new(NoteLiteral::B, None), vec![11, 2, 5, 9], "Bmin7(b5)")]
+#[test_case(Note::new(NoteLiteral::B, None), vec![11, 2, 5, 8], "Bdim7")]
+#[test_case(Note::new(NoteLiteral::C, None), vec![0, 4, 8], "C(#5)")]
+#[test_case(Note::new(NoteLiteral::C, None), vec![0, 5, 7], "Csus")]
+#[test_case(Note::new(NoteLiteral::C, None), vec![0, 2, 7], "C(add9,omit3)")]
+#[test_case(Note::new(NoteLiteral::C, None), vec![0, 3, 4, 7, 10], "C7(#9)")]
+#[test_case(Note::new(NoteLiteral::C, None), vec![0, 4, 6, 7, 10], "C7(#11)")]
+#[test_case(Note::new(NoteLiteral::C, None), vec![0, 4, 7, 10, 2, 9], "C13")]
+#[test_case(Note::new(NoteLiteral::E, Some(Modifier::Flat)), vec![15, 19, 22, 26], "EbMaj7")]
+fn test_from_pitch_classes(root: Note, pcs: Vec<u8>, expected: &str) {
+    let chord = Chord::from_pitch_classes(root, &pcs);
+    assert_eq!(chord.normalized, expected);
+}
+
+#[test_case("C")]
+#[test_case("F#m7b5")]
+#[test_case("Bb13(#11)")]
+#[test_case("Ebsus2")]
+#[test_case("G7sus")]
+#[test_case("Abm(Maj7)/Eb")]
+#[test_case("D5")]
+#[test_case("Cbdim7/Bbb")]
+#[test_case("E7alt")]
+fn test_bytes_round_trip(input: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let bytes = chord.to_bytes();
+    assert_eq!(bytes.len(), 7);
+    let decoded = Chord::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.normalized, chord.normalized);
+    assert_eq!(decoded.notes, chord.notes);
+    assert_eq!(decoded.bass, chord.bass);
+    assert_eq!(decoded.real_intervals, chord.real_intervals);
+    assert_eq!(decoded.quality, chord.quality);
+    assert_eq!(decoded.to_bytes(), bytes);
+}
+
+#[test_case(&[], None)]
+#[test_case(&[0, 0xFF, 0x11, 1, 0, 0], None)]
+#[test_case(&[7, 0xFF, 0x11, 1, 0, 0, 0], None)]
+#[test_case(&[0x50, 0xFF, 0x11, 1, 0, 0, 0], None)]
+#[test_case(&[0, 0xFF, 0x11, 1, 0, 0x80, 0], None)]
+#[test_case(&[0, 0xFF, 0x11, 1, 0, 0, 1], None)]
+#[test_case(&[0, 0xFF, 0x11, 1, 0, 0, 0], Some("C"))]
+#[test_case(&[0x21, 0x22, 0x11, 0x21, 0, 0, 2], Some("Db7/Eb"))]
+fn test_from_bytes(bytes: &[u8], expected: Option<&str>) {
+    let chord = Chord::from_bytes(bytes);
+    assert_eq!(chord.as_ref().map(|c| c.normalized.as_str()), expected);
+}
+
+#[test_case(vec!["C", "E", "G"], vec!["C", "C6", "C(add9)", "CMaj7", "C7"])]
+#[test_case(vec!["E", "Bb"], vec!["Edim", "Bbdim", "Emin7(b5)", "Bbmin7(b5)"])]
+#[test_case(vec!["C", "E", "G", "Bb"], vec!["C7", "C9", "C13"])]
+#[test_case(vec!["F#", "A#", "C#"], vec!["F#", "F#6", "F#(add9)"])]
+#[test_case(vec!["C", "Db", "D"], vec![])]
+#[test_case(vec![], vec![])]
+fn test_chord_names_containing(notes: Vec<&str>, expected: Vec<&str>) {
+    let mut parser = Parser::new();
+    let notes = notes
+        .iter()
+        .map(|n| parser.parse(n).unwrap().root)
+        .collect::<Vec<Note>>();
+    let names = chord_names_containing(&notes);
+    assert_eq!(names.is_empty(), expected.is_empty());
+    assert_eq!(&names[..expected.len().min(names.len())], expected);
+    for name in names {
+        let chord = parser.parse(&name).unwrap();
+        for note in &notes {
+            assert!(chord
+                .notes
+                .iter()
+                .any(|n| n.to_semitone() == note.to_semitone()));
+        }
+    }
+}
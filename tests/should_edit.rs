@@ -0,0 +1,316 @@
+use chordparser::{
+    chord::{
+        intervals::{Direction, Interval, SemInterval},
+        note::{Modifier, Note, NoteLiteral},
+    },
+    parsing::Parser,
+};
+use test_case::test_case;
+
+/// This test suite covers the helpers used to edit an already parsed chord without re-parsing it.
+
+#[test_case("C/E", "C")]
+#[test_case("Cm7/Bb", "Cmin7")]
+#[test_case("C7(b9)/E", "C7(b9)")]
+#[test_case("CMaj7", "CMaj7")]
+fn test_without_bass(input: &str, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let edited = chord.without_bass();
+    assert_eq!(edited.bass, None);
+    assert_eq!(edited.normalized, expected);
+    assert_eq!(edited.real_intervals, chord.real_intervals);
+}
+
+#[test_case("C", Note::new(NoteLiteral::E, None), "C/E")]
+#[test_case("Cm7", Note::new(NoteLiteral::B, Some(Modifier::Flat)), "Cmin7/Bb")]
+#[test_case("C/G", Note::new(NoteLiteral::E, None), "C/E")]
+fn test_with_bass(input: &str, bass: Note, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let edited = chord.with_bass(bass.clone());
+    assert_eq!(edited.bass, Some(bass));
+    assert_eq!(edited.normalized, expected);
+    assert_eq!(edited.real_intervals, chord.real_intervals);
+}
+
+#[test_case("G7", Some("Db7"))]
+#[test_case("G7(b9)", Some("Db7(b9)"))]
+#[test_case("G13", Some("Db13"))]
+#[test_case("G7alt", Some("Db7(b9,#9,#11,b13)"))]
+#[test_case("C7", Some("Gb7"))]
+#[test_case("Db7", Some("G7"))]
+#[test_case("G7/B", Some("Db7/F"))]
+#[test_case("G7sus", None)]
+#[test_case("GMaj7", None)]
+#[test_case("Gm7", None)]
+#[test_case("G", None)]
+fn test_tritone_sub(input: &str, expected: Option<&str>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let sub = chord.tritone_sub();
+    assert_eq!(sub.as_ref().map(|c| c.normalized.as_str()), expected);
+    if let Some(sub) = sub {
+        assert_eq!(sub.real_intervals, chord.real_intervals);
+        assert_eq!(sub.quality, chord.quality);
+    }
+}
+
+#[test_case("CMaj7", Note::new(NoteLiteral::D, Some(Modifier::Flat)), "DbMaj7")]
+#[test_case(
+    "Cm7b5",
+    Note::new(NoteLiteral::F, Some(Modifier::Sharp)),
+    "F#min7(b5)"
+)]
+#[test_case("C6", Note::new(NoteLiteral::E, None), "E6")]
+fn test_transpose_keeps_quality(input: &str, to: Note, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let transposed = chord.transpose_to(&to);
+    assert_eq!(transposed.normalized, expected);
+    assert_eq!(transposed.quality, chord.quality);
+}
+
+#[test_case("G/B", Note::new(NoteLiteral::A, None), "C#", "A/C#")]
+#[test_case("C/E", Note::new(NoteLiteral::E, Some(Modifier::Flat)), "G", "Eb/G")]
+#[test_case("D/F#", Note::new(NoteLiteral::B, Some(Modifier::Flat)), "D", "Bb/D")]
+#[test_case("C/Bb", Note::new(NoteLiteral::E, None), "D", "E/D")]
+#[test_case(
+    "Cmaj7/B",
+    Note::new(NoteLiteral::D, Some(Modifier::Flat)),
+    "C",
+    "DbMaj7/C"
+)]
+#[test_case("B/B", Note::new(NoteLiteral::F, Some(Modifier::Sharp)), "F#", "F#/F#")]
+fn test_transpose_to_bass(input: &str, to: Note, bass: &str, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let transposed = chord.transpose_to(&to);
+    assert_eq!(transposed.bass.unwrap().to_string(), bass);
+    assert_eq!(transposed.normalized, expected);
+}
+
+#[test_case("Cm7b5", Note::new(NoteLiteral::D, None), "Dmin7(b5)")]
+#[test_case("C#5", Note::new(NoteLiteral::D, None), "D5")]
+#[test_case("C(#5)", Note::new(NoteLiteral::E, Some(Modifier::Flat)), "Eb(#5)")]
+#[test_case("C5", Note::new(NoteLiteral::F, Some(Modifier::Sharp)), "F#5")]
+#[test_case("Cmaj7#11/E", Note::new(NoteLiteral::A, None), "AMaj7(#11)/C#")]
+#[test_case("C7 ( b9 , #9 )", Note::new(NoteLiteral::G, None), "G7(b9,#9)")]
+#[test_case(
+    "Cm(add9)",
+    Note::new(NoteLiteral::B, Some(Modifier::Flat)),
+    "Bbmin(add9)"
+)]
+fn test_transposed_symbol(input: &str, to: Note, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let symbol = chord.transposed_symbol(&to);
+    assert_eq!(symbol, expected);
+    let reparsed = parser.parse(&symbol).unwrap();
+    assert_eq!(reparsed.normalized, symbol);
+    assert_eq!(reparsed.real_intervals, chord.real_intervals);
+}
+
+#[test_case("C", 0, "C")]
+#[test_case("C", 1, "Db")]
+#[test_case("C", -1, "B")]
+#[test_case("C#m7", 2, "D#min7")]
+#[test_case("Bb7", 2, "C7")]
+#[test_case("Bb7", 3, "Db7")]
+#[test_case("G/B", 14, "A/C#")]
+#[test_case("Fmaj7", -13, "EMaj7")]
+#[test_case("Ebm", 12, "Ebmin")]
+fn test_transpose_by_semitones(input: &str, semitones: i8, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let transposed = chord.transpose_by_semitones(semitones);
+    assert_eq!(transposed.normalized, expected);
+    assert_eq!(transposed.real_intervals, chord.real_intervals);
+}
+
+#[test_case("C", Interval::PerfectFourth, Direction::Up, "F")]
+#[test_case("C", Interval::AugmentedFourth, Direction::Up, "F#")]
+#[test_case("C", Interval::DiminishedFifth, Direction::Up, "Gb")]
+#[test_case("C", Interval::PerfectFourth, Direction::Down, "G")]
+#[test_case("C", Interval::MinorThird, Direction::Down, "A")]
+#[test_case("F#m7", Interval::MajorThird, Direction::Up, "A#min7")]
+#[test_case("Eb7", Interval::MinorSecond, Direction::Up, "Fb7")]
+#[test_case("D/F#", Interval::MajorSecond, Direction::Down, "C/E")]
+#[test_case("G7", Interval::Ninth, Direction::Up, "A7")]
+#[test_case("Bbmaj7", Interval::Unison, Direction::Down, "BbMaj7")]
+#[test_case("A", Interval::Octave, Direction::Up, "A")]
+fn test_transpose_by_interval(
+    input: &str,
+    interval: Interval,
+    direction: Direction,
+    expected: &str,
+) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let transposed = chord.transpose_by_interval(interval, direction);
+    assert_eq!(transposed.normalized, expected);
+    assert_eq!(transposed.real_intervals, chord.real_intervals);
+}
+
+#[test_case("G", 2, "A")]
+#[test_case("Em7", 3, "Gmin7")]
+#[test_case("D/F#", 5, "G/B")]
+#[test_case("Am", -2, "Gmin")]
+fn test_parse_with_transpose(input: &str, semitones: i8, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse_with_transpose(input, semitones).unwrap();
+    assert_eq!(chord.normalized, expected);
+    assert!(parser.parse_with_transpose("H7", 2).is_err());
+}
+
+#[test_case("C7", SemInterval::Fifth, "C7(omit5)", vec!["C", "E", "Bb"])]
+#[test_case("C7(b5,#5)", SemInterval::Fifth, "C7(omit5)", vec!["C", "E", "Bb"])]
+#[test_case("Cmaj7", SemInterval::Third, "CMaj7(omit3)", vec!["C", "G", "B"])]
+#[test_case("C9", SemInterval::Ninth, "C7", vec!["C", "E", "G", "Bb"])]
+#[test_case("C/E", SemInterval::Fifth, "C(omit5)/E", vec!["C", "E"])]
+#[test_case("C", SemInterval::Root, "C", vec!["C", "E", "G"])]
+fn test_with_omitted(input: &str, degree: SemInterval, expected: &str, notes: Vec<&str>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let edited = chord.with_omitted(degree);
+    assert_eq!(edited.normalized, expected);
+    assert_eq!(edited.note_literals, notes);
+    assert_eq!(
+        edited.real_intervals,
+        parser.parse(expected).unwrap().real_intervals
+    );
+}
+
+#[test_case("C", Interval::Ninth, "C(add9)", vec!["C", "E", "G", "D"])]
+#[test_case("Cm7", Interval::Eleventh, "Cmin7(add11)", vec!["C", "Eb", "G", "Bb", "F"])]
+#[test_case("C7", Interval::FlatNinth, "C7(b9)", vec!["C", "E", "G", "Bb", "Db"])]
+#[test_case("C", Interval::MajorThird, "C", vec!["C", "E", "G"])]
+fn test_with_added(input: &str, interval: Interval, expected: &str, notes: Vec<&str>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let edited = chord.with_added(interval);
+    assert_eq!(edited.normalized, expected);
+    assert_eq!(edited.note_literals, notes);
+    assert_eq!(edited.real_intervals.first(), Some(&Interval::Unison));
+}
+
+#[test_case("C", "C")]
+#[test_case("Cmaj9", "C")]
+#[test_case("Cm7", "Cmin")]
+#[test_case("C13(#11)", "C")]
+#[test_case("Cdim7", "Cdim")]
+#[test_case("Cm7b5", "Cdim")]
+#[test_case("C7(#5)", "C(#5)")]
+#[test_case("C+", "C(#5)")]
+#[test_case("C6", "C")]
+#[test_case("Cm6/9", "Cmin")]
+#[test_case("C9sus", "Csus")]
+#[test_case("C11", "Csus")]
+#[test_case("C7sus2", "C(add9,omit3)")]
+#[test_case("C5", "C5")]
+#[test_case("C7(omit5)", "C(omit5)")]
+#[test_case("Cm9/Bb", "Cmin/Bb")]
+fn test_triad(input: &str, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let triad = chord.triad();
+    assert_eq!(triad.normalized, expected);
+    assert!(triad.real_intervals.len() <= 3);
+}
+
+#[test_case("C#7", Some("Db7"), vec!["Db", "F", "Ab", "Cb"])]
+#[test_case("Db7", Some("C#7"), vec!["C#", "E#", "G#", "B"])]
+#[test_case("Gbm/Bbb", Some("F#min/A"), vec!["F#", "A", "C#"])]
+#[test_case("B#", Some("C"), vec!["C", "E", "G"])]
+#[test_case("D7", None, vec![])]
+fn test_respell_root(input: &str, expected: Option<&str>, notes: Vec<&str>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let respelled = chord.respell_root();
+    assert_eq!(respelled.as_ref().map(|c| c.normalized.as_str()), expected);
+    if let Some(respelled) = respelled {
+        assert_eq!(respelled.note_literals, notes);
+        assert_eq!(respelled.real_intervals, chord.real_intervals);
+    }
+}
+
+#[test_case("G7", Note::new(NoteLiteral::D, None), 5, vec![62, 78, 81, 84])]
+#[test_case("C", Note::new(NoteLiteral::C, None), 4, vec![48, 64, 67])]
+#[test_case("C", Note::new(NoteLiteral::C, None), 3, vec![36, 52, 55])]
+#[test_case("Am/C", Note::new(NoteLiteral::B, None), 2, vec![26, 47, 50, 54])]
+#[test_case("C", Note::new(NoteLiteral::B, Some(Modifier::Sharp)), 4, vec![60, 76, 79])]
+#[test_case("C", Note::new(NoteLiteral::C, Some(Modifier::Flat)), 4, vec![47, 63, 66])]
+#[test_case("C", Note::new(NoteLiteral::C, None), 0, vec![12, 28, 31])]
+#[test_case("C13", Note::new(NoteLiteral::B, None), 12, vec![107, 123, 126, 117, 121, 116])]
+fn test_transpose_to_octave(input: &str, to: Note, octave: u8, expected: Vec<u8>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let transposed = chord.transpose_to_octave(&to, octave);
+    assert_eq!(transposed.to_midi_codes(), expected);
+    assert_eq!(transposed.root, to);
+    assert_eq!(transposed.octave(), octave.clamp(1, 8));
+    assert_eq!(
+        transposed.transpose_to(&chord.root).octave(),
+        transposed.octave()
+    );
+}
+
+#[test_case("Cb", 1, vec![11, 27, 30])]
+#[test_case("Cbb/Cbb", 1, vec![10, 22, 26, 29])]
+#[test_case("Cb13", 1, vec![11, 27, 30, 33, 37, 44])]
+#[test_case("C13", 8, vec![96, 112, 115, 118, 122, 117])]
+#[test_case("B#13(#11)", 8, vec![108, 124, 127, 118, 122, 126, 117])]
+#[test_case("B#/B#", 8, vec![108, 120, 124, 127])]
+fn test_midi_codes_in_range(input: &str, octave: u8, expected: Vec<u8>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let chord = chord.transpose_to_octave(&chord.root, octave);
+    let codes = chord.to_midi_codes();
+    assert_eq!(codes, expected);
+    assert!(codes.iter().all(|code| *code <= 127));
+    let notes = chord.bass.iter().chain(&chord.notes);
+    assert!(codes
+        .iter()
+        .zip(notes)
+        .all(|(code, note)| code % 12 == note.to_semitone()));
+    assert_eq!(chord.has_doublings(), chord.bass.is_some());
+}
+
+#[test_case("Am7/C", "C6")]
+#[test_case("Em/C", "CMaj7")]
+#[test_case("Dm7/G", "G9sus")]
+#[test_case("Ebdim7/A", "Adim7")]
+#[test_case("C/E", "C/E")]
+#[test_case("C/Bb", "C/Bb")]
+#[test_case("F/G", "F/G")]
+#[test_case("Am7", "Amin7")]
+fn test_simplest_spelling(input: &str, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.simplest_spelling().normalized, expected);
+}
+
+#[test_case("C", false, vec![36, 48, 52, 55])]
+#[test_case("C", true, vec![36, 48, 52, 55, 67])]
+#[test_case("Cm7", true, vec![36, 48, 51, 55, 58, 67])]
+#[test_case("C7(b5)", true, vec![36, 48, 52, 54, 58, 66])]
+#[test_case("C/E", false, vec![40, 48, 52, 55, 60])]
+#[test_case("C/E", true, vec![40, 48, 52, 55, 60, 67])]
+#[test_case("C(omit5)", true, vec![36, 48, 52])]
+#[test_case("C5", true, vec![36, 48, 55, 67])]
+fn test_doubled_voicing(input: &str, double_fifth: bool, expected: Vec<u8>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.doubled_voicing(double_fifth), expected);
+}
+
+#[test_case("C", 8, vec![96, 108, 112, 115, 127])]
+#[test_case("G", 8, vec![103, 115, 119, 122])]
+#[test_case("B#/B#", 8, vec![108, 120, 124, 127])]
+#[test_case("B#13(#11)", 8, vec![108, 117, 118, 120, 122, 124, 126, 127])]
+fn test_doubled_voicing_in_range(input: &str, octave: u8, expected: Vec<u8>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let chord = chord.transpose_to_octave(&chord.root, octave);
+    assert_eq!(chord.doubled_voicing(true), expected);
+}
@@ -1,6 +1,9 @@
 use core::panic;
 
-use chordparser::parsing::Parser;
+use chordparser::{
+    chord::note::{Note, NoteLiteral},
+    parsing::{parser_config::ParserConfig, parser_error::ParserError, Parser},
+};
 use test_case::test_case;
 
 #[test_case("CMaj7randomb5", vec![])]
@@ -50,3 +53,262 @@ fn should_error(i: &str, _expected: Vec<&str>) {
         }
     }
 }
+
+#[test_case("CMaj7b9", vec!["B", "C", "Db"])]
+#[test_case("C(add2,b9)", vec!["C", "Db", "D"])]
+#[test_case("CMaj7(add2)b9", vec!["C", "Db", "D", "B", "C", "Db"])]
+#[test_case("Cmi(add9)b9", vec!["C", "Db", "D", "Db", "D", "Eb"])]
+#[test_case("F#Maj7b9", vec!["E#", "F#", "G"])]
+fn should_error_three_consecutive_semitones(i: &str, expected: Vec<&str>) {
+    let mut parser = Parser::new();
+    let res = parser.parse(i);
+    match res {
+        Ok(chord) => panic!("Expected an error, got {:?}", chord),
+        Err(e) => {
+            let notes: Vec<String> = e
+                .errors
+                .iter()
+                .filter_map(|e| match e {
+                    ParserError::ThreeConsecutiveSemitones(notes) => Some(notes.clone()),
+                    _ => None,
+                })
+                .flatten()
+                .collect();
+            assert_eq!(notes, expected);
+        }
+    }
+}
+
+#[test_case("C7b9#9")]
+#[test_case("C7(b9,#9)")]
+#[test_case("C13(b9,#9)")]
+#[test_case("C7b5#5b9#9b13")]
+#[test_case("Calt")]
+#[test_case("CMaj7#9")]
+#[test_case("CminMaj7")]
+fn should_not_error_dense_chords(i: &str) {
+    let mut parser = Parser::new();
+    if let Err(e) = parser.parse(i) {
+        panic!("{e}");
+    }
+}
+
+#[test_case("C(add9", vec![ParserError::MissingClosingParenthesis(7)])]
+#[test_case("C7(b9,#11", vec![ParserError::MissingClosingParenthesis(10)])]
+#[test_case("C△(9", vec![ParserError::MissingClosingParenthesis(5)])]
+#[test_case("C)", vec![ParserError::UnexpectedClosingParenthesis(2)])]
+#[test_case("C7(b9))", vec![ParserError::UnexpectedClosingParenthesis(7)])]
+#[test_case("C((9))", vec![ParserError::NestedParenthesis(3)])]
+#[test_case("C7(b9(#11))", vec![ParserError::NestedParenthesis(6)])]
+fn should_error_parenthesis_at_position(input: &str, expected: Vec<ParserError>) {
+    let mut parser = Parser::new();
+    let res = parser.parse(input);
+    assert_eq!(res.unwrap_err().errors, expected);
+}
+
+#[test_case("C5add9")]
+#[test_case("C5add9/G")]
+#[test_case("C5/9")]
+#[test_case("C57")]
+fn should_error_invalid_power_expression(input: &str) {
+    let mut parser = Parser::new();
+    let res = parser.parse(input);
+    assert!(res
+        .unwrap_err()
+        .errors
+        .contains(&ParserError::InvalidPowerExpression));
+}
+
+#[test_case(""; "empty")]
+#[test_case("   "; "whitespace")]
+#[test_case("\t\n"; "tab and newline")]
+#[test_case("#"; "lone sharp")]
+#[test_case("b"; "lone flat")]
+#[test_case("♭"; "lone flat sign")]
+#[test_case(" # "; "padded lone sharp")]
+fn should_error_missing_root_only(input: &str) {
+    let mut parser = Parser::new();
+    let res = parser.parse(input);
+    assert_eq!(res.unwrap_err().errors, vec![ParserError::MissingRootNote]);
+}
+
+#[test_case("m7")]
+#[test_case("7")]
+#[test_case("/E")]
+#[test_case("(b9)")]
+fn should_error_missing_root(input: &str) {
+    let mut parser = Parser::new();
+    let res = parser.parse(input);
+    assert!(res
+        .unwrap_err()
+        .errors
+        .contains(&ParserError::MissingRootNote));
+}
+
+#[test_case("", vec![ParserError::MissingRootNote])]
+#[test_case("X7", vec![ParserError::InvalidRomanNumeral(1)])]
+#[test_case("IIII", vec![ParserError::InvalidRomanNumeral(1)])]
+#[test_case("Ii7", vec![ParserError::InvalidRomanNumeral(1)])]
+#[test_case("bbVI", vec![ParserError::InvalidRomanNumeral(3)])]
+#[test_case(" #", vec![ParserError::InvalidRomanNumeral(3)])]
+#[test_case("V7(", vec![ParserError::MissingClosingParenthesis(4)])]
+#[test_case("viio7(", vec![ParserError::MissingClosingParenthesis(7)])]
+#[test_case("bVII7(", vec![ParserError::MissingClosingParenthesis(7)])]
+#[test_case("  ii7(", vec![ParserError::MissingClosingParenthesis(7)]; "padded")]
+#[test_case("ii7(b9,b9)", vec![ParserError::DuplicateExtension(8)])]
+#[test_case("IV7x", vec![ParserError::IllegalToken(4)])]
+#[test_case(
+    "iiadd3",
+    vec![ParserError::InconsistentExtension {
+        first: "b3".to_string(),
+        second: "3".to_string(),
+        pos: 6,
+    }]
+)]
+fn should_error_parse_degree(input: &str, expected: Vec<ParserError>) {
+    let mut parser = Parser::new();
+    let key = Note::new(NoteLiteral::C, None);
+    let res = parser.parse_degree(input, &key);
+    assert_eq!(res.unwrap_err().errors, expected);
+}
+
+#[test_case("Cmajj7", vec![ParserError::IllegalToken(5)])]
+#[test_case("Cmaji", vec![ParserError::IllegalToken(5)])]
+#[test_case("CmA7", vec![ParserError::UnexpectedNote(3)])]
+fn should_error_partial_major_keyword(input: &str, expected: Vec<ParserError>) {
+    let mut parser = Parser::new();
+    let res = parser.parse(input);
+    assert_eq!(res.unwrap_err().errors, expected);
+}
+
+#[test_case("Csus4(add9,3)", vec![ParserError::SusWithThird(12)])]
+#[test_case("Csus(add3)", vec![ParserError::SusWithThird(9)])]
+#[test_case("Csus2add3", vec![ParserError::SusWithThird(9)])]
+#[test_case("C7sus4add3/G", vec![ParserError::SusWithThird(10)])]
+fn should_error_sus_with_third(input: &str, expected: Vec<ParserError>) {
+    let config = ParserConfig {
+        reject_sus_with_third: true,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    let res = parser.parse(input);
+    assert_eq!(res.unwrap_err().errors, expected);
+    assert!(Parser::new().parse(input).is_ok());
+}
+
+#[test_case("C7b9add9", ("b9", "9", 8), vec!["C", "Db", "D"])]
+#[test_case("C7(b9,add9)", ("b9", "9", 10), vec!["C", "Db", "D"])]
+#[test_case("Cadd9b9", ("9", "b9", 6), vec!["C", "Db", "D"])]
+#[test_case("C7#9add9", ("#9", "9", 8), vec!["D", "D#", "E"])]
+#[test_case("C9b9", ("9", "b9", 3), vec!["C", "Db", "D"])]
+fn should_error_add9_with_altered_ninth(
+    input: &str,
+    (first, second, pos): (&str, &str, usize),
+    semitones: Vec<&str>,
+) {
+    let mut parser = Parser::new();
+    let res = parser.parse(input);
+    assert_eq!(
+        res.unwrap_err().errors,
+        vec![
+            ParserError::InconsistentExtension {
+                first: first.to_string(),
+                second: second.to_string(),
+                pos,
+            },
+            ParserError::ThreeConsecutiveSemitones(
+                semitones.iter().map(|s| s.to_string()).collect()
+            ),
+        ]
+    );
+}
+
+#[test_case("C9(#11,11)", "#11", "11", 8)]
+#[test_case("C11#11", "11", "#11", 4)]
+#[test_case("C13b13", "13", "b13", 4)]
+#[test_case("CAlt9", "b9", "9", 5)]
+#[test_case("Cm6b6", "6", "b6", 4)]
+#[test_case("Cm(add3)", "b3", "3", 7)]
+fn should_error_inconsistent_extension(input: &str, first: &str, second: &str, pos: usize) {
+    let mut parser = Parser::new();
+    let errors = parser.parse(input).unwrap_err().errors;
+    let expected = ParserError::InconsistentExtension {
+        first: first.to_string(),
+        second: second.to_string(),
+        pos,
+    };
+    assert_eq!(errors[0], expected);
+    assert_eq!(errors[0].error_position(), Some(pos));
+}
+
+#[test_case("Cm##9", vec![ParserError::InvalidExtension(5)])]
+#[test_case("Cm7(##5)", vec![ParserError::InvalidExtension(7)])]
+#[test_case("Cmbbb7", vec![ParserError::UnexpectedModifier(3)])]
+#[test_case("Cm(addbb7)", vec![ParserError::WrongExpressionTarget(9)])]
+#[test_case("Cm6bb7", vec![ParserError::DuplicateExtension(4)])]
+#[test_case(
+    "C7bb7",
+    vec![ParserError::InconsistentExtension {
+        first: "7".to_string(),
+        second: "bb7".to_string(),
+        pos: 3,
+    }]
+)]
+fn should_error_double_accidental_extension(input: &str, expected: Vec<ParserError>) {
+    let mut parser = Parser::new();
+    let res = parser.parse(input);
+    assert_eq!(res.unwrap_err().errors, expected);
+}
+
+#[test_case("C/G/E", vec![ParserError::IllegalSlashNotation(4)])]
+#[test_case("C/E/Eb", vec![ParserError::IllegalSlashNotation(4)])]
+#[test_case("Cm7/Bb/Ab", vec![ParserError::IllegalSlashNotation(7)])]
+#[test_case("C/G/", vec![ParserError::IllegalSlashNotation(4)])]
+#[test_case("C / G / E", vec![ParserError::IllegalSlashNotation(7)])]
+#[test_case("C6/9/E", vec![ParserError::IllegalSlashNotation(5)])]
+fn should_error_multiple_slashes(input: &str, expected: Vec<ParserError>) {
+    let mut parser = Parser::new();
+    let res = parser.parse(input);
+    assert_eq!(res.unwrap_err().errors, expected);
+}
+
+#[test_case("Cmaj7", vec![ParserError::MissingRootNote, ParserError::IllegalToken(1)])]
+#[test_case("DoA", vec![ParserError::IllegalToken(3)])]
+#[test_case("do", vec![ParserError::MissingRootNote, ParserError::IllegalToken(1)])]
+fn should_error_solfege_notes(input: &str, expected: Vec<ParserError>) {
+    let config = ParserConfig {
+        solfege_notes: true,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    let res = parser.parse(input);
+    assert_eq!(res.unwrap_err().errors, expected);
+}
+
+#[test_case("C minor sevenn", (9..15).map(ParserError::IllegalToken).collect())]
+#[test_case("C seven flat four", vec![ParserError::InvalidExtension(14)])]
+fn should_error_spelled_out_words(input: &str, expected: Vec<ParserError>) {
+    let config = ParserConfig {
+        spelled_out_words: true,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    let res = parser.parse(input);
+    assert_eq!(res.unwrap_err().errors, expected);
+}
+
+#[test_case("C minor seven", (9..14).map(ParserError::IllegalToken).collect())]
+fn should_error_spelled_out_words_by_default(input: &str, expected: Vec<ParserError>) {
+    let mut parser = Parser::new();
+    let res = parser.parse(input);
+    assert_eq!(res.unwrap_err().errors, expected);
+}
+
+#[test_case("m7(", vec![ParserError::MissingClosingParenthesis(4)])]
+#[test_case("x7", vec![ParserError::IllegalToken(1)])]
+fn should_error_parse_descriptor(input: &str, expected: Vec<ParserError>) {
+    let mut parser = Parser::new();
+    let root = Note::new(NoteLiteral::C, None);
+    let res = parser.parse_descriptor(root, input);
+    assert_eq!(res.unwrap_err().errors, expected);
+}
@@ -1,7 +1,9 @@
-use chordparser::parsing::Parser;
+use chordparser::{chord::normalize::NamingStyle, parsing::Parser};
 use test_case::test_case;
 
 #[test_case("C5", "C5")]
+#[test_case("C5/G", "C5/G")]
+#[test_case("Cno3/E", "C5/E")]
 #[test_case("C6Maj7", "C6(addMaj7)")]
 #[test_case("CMaj7#9omit3", "CMaj7(#9,omit3)")]
 #[test_case("Cmaj7no3", "CMaj7(omit3)")]
@@ -113,3 +115,152 @@ fn test_normalize(input: &str, expected: &str) {
         }
     }
 }
+
+#[test_case("CΔ7", "CΔ7"; "delta seventh")]
+#[test_case("C△9", "C△9"; "triangle ninth")]
+#[test_case("C^7", "C^7"; "caret seventh")]
+#[test_case("C-7", "C-7"; "hyphen minor seventh")]
+#[test_case("C-Maj7", "C-Maj7"; "hyphen minor major seventh")]
+#[test_case("Cmi7(b5)", "Cmi7(b5)"; "mi half diminished")]
+#[test_case("Cma7#11", "Cma7(#11)"; "ma seventh sharp eleventh")]
+#[test_case("C°7", "C°7"; "degree sign diminished seventh")]
+#[test_case("Co", "Co"; "o diminished")]
+#[test_case("C13b9#9", "C13(b9,#9)"; "altered thirteenth")]
+#[test_case("Cm/Bb", "Cm/Bb"; "minor over bass")]
+fn test_preserve_input(input: &str, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.normalized_with(NamingStyle::PreserveInput), expected);
+}
+
+#[test_case("CΔ7")]
+#[test_case("C-7")]
+#[test_case("Cdim7")]
+fn test_normalized_style(input: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(
+        chord.normalized_with(NamingStyle::Normalized),
+        chord.normalized
+    );
+}
+
+#[test_case("CMIN7", "CMIN7"; "upper case min")]
+#[test_case("Cmin7", "Cmin7"; "lower case min")]
+#[test_case("CMin7", "CMin7"; "capitalized min")]
+#[test_case("Cmi7", "Cmin7"; "lower case mi")]
+#[test_case("Cm7", "Cmin7"; "lower case m")]
+#[test_case("CM7", "CMaj7"; "upper case m")]
+#[test_case("Cmaj7", "Cmaj7"; "lower case maj")]
+#[test_case("CMAJ7", "CMAJ7"; "upper case maj")]
+#[test_case("CMA7", "CMAJ7"; "upper case ma")]
+#[test_case("CMa7", "CMaj7"; "capitalized ma")]
+#[test_case("CDIM7", "CDIM7"; "upper case dim")]
+#[test_case("C7SUS4", "C7SUS"; "upper case sus")]
+#[test_case("CMIN(ADD9)", "CMIN(ADD9)"; "upper case add")]
+#[test_case("C7(NO3)", "C7(OMIT3)"; "upper case no")]
+#[test_case("C7(Omit3)", "C7(Omit3)"; "capitalized omit")]
+#[test_case("C-7", "Cmin7"; "hyphen")]
+fn test_match_case(input: &str, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.normalized_with(NamingStyle::MatchCase), expected);
+}
+
+#[test_case("C6/9", "C6/9", "C69"; "slash")]
+#[test_case("C69", "C6/9", "C69"; "digits")]
+#[test_case("C6add9", "C6/9", "C69"; "add")]
+#[test_case("C6(add9)", "C6/9", "C69"; "add group")]
+#[test_case("C6 add 9", "C6/9", "C69"; "spaced add")]
+#[test_case("C6(9)", "C6/9", "C69"; "ninth group")]
+#[test_case("Cm6add9", "Cm6/9", "Cmin69"; "minor add")]
+#[test_case("Bb6add9", "Bb6/9", "Bb69"; "flat root add")]
+fn test_six_nine(input: &str, slash: &str, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let slash = parser.parse(slash).unwrap();
+    assert_eq!(chord.normalized, expected);
+    assert_eq!(slash.normalized, expected);
+    assert_eq!(chord.real_intervals, slash.real_intervals);
+    assert_eq!(chord.note_literals, slash.note_literals);
+}
+
+#[test_case("C7(b9)", "C7b9")]
+#[test_case("Cmaj7#11", "CMaj7#11")]
+#[test_case("Cm7b5", "Cmin7b5")]
+#[test_case("C7(b9)/E", "C7b9/E")]
+#[test_case("C7sus4(b9)", "C7sus(b9)")]
+#[test_case("C6(b5)", "C6b5")]
+#[test_case("C7(b9,#9)", "C7(b9,#9)")]
+#[test_case("C(b5)", "C(b5)")]
+#[test_case("C(#5)", "C(#5)")]
+#[test_case("Cadd9", "C(add9)")]
+#[test_case("C7(omit3)", "C7(omit3)")]
+#[test_case("Cm9", "Cmin9")]
+fn test_compact(input: &str, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let compact = chord.normalized_with(NamingStyle::Compact);
+    assert_eq!(compact, expected);
+    let reparsed = parser.parse(&compact).unwrap();
+    assert_eq!(reparsed.normalized, chord.normalized);
+}
+
+#[test_case("C", "", "")]
+#[test_case("Cm7/Bb", "m7/Bb", "min7")]
+#[test_case("C-7b5", "-7b5", "min7(b5)")]
+#[test_case("F#maj7#11", "maj7#11", "Maj7(#11)")]
+#[test_case("Bb5/F", "5/F", "5")]
+#[test_case("Ebsus4add9", "sus4add9", "sus(add9)")]
+fn test_descriptors(input: &str, descriptor: &str, normalized: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.descriptor_only(), descriptor);
+    assert_eq!(chord.normalized_descriptor(), normalized);
+    assert_eq!(
+        format!("{}{}", chord.root, chord.normalized_descriptor()),
+        chord.without_bass().normalized
+    );
+}
+
+/// Returns every ordering of the given items.
+fn permutations(items: &[&str]) -> Vec<Vec<String>> {
+    if items.len() <= 1 {
+        return vec![items.iter().map(|s| s.to_string()).collect()];
+    }
+    let mut res = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        let mut rest = items.to_vec();
+        rest.remove(i);
+        for mut perm in permutations(&rest) {
+            perm.insert(0, item.to_string());
+            res.push(perm);
+        }
+    }
+    res
+}
+
+#[test_case("C7", &["#5", "#9"], "C7(#5,#9)")]
+#[test_case("C7", &["b9", "#11", "#5"], "C7(#5,b9,#11)")]
+#[test_case("C13", &["b9", "#11"], "C13(b9,#11)")]
+#[test_case("C7", &["b5", "b9", "b13"], "C7(b5,b9,b13)")]
+#[test_case("C7", &["#9", "b13", "b9"], "C7(b9,#9,b13)")]
+#[test_case("Cm7", &["b5", "b9"], "Cmin7(b5,b9)")]
+#[test_case("CMaj7", &["#11", "#5"], "CMaj7(#5,#11)")]
+fn test_alteration_order(base: &str, alterations: &[&str], expected: &str) {
+    let mut parser = Parser::new();
+    let reference = parser.parse(expected).unwrap();
+    for perm in permutations(alterations) {
+        let inputs = [
+            format!("{}{}", base, perm.join("")),
+            format!("{}({})", base, perm.join(",")),
+            format!("{}{}({})", base, perm[0], perm[1..].join(",")),
+        ];
+        for input in inputs {
+            let chord = parser.parse(&input).unwrap();
+            assert_eq!(chord.normalized, expected, "{}", input);
+            assert_eq!(chord.notes, reference.notes, "{}", input);
+            assert_eq!(chord.real_intervals, reference.real_intervals, "{}", input);
+        }
+    }
+}
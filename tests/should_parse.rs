@@ -1,6 +1,13 @@
 use chordparser::{
-    chord::note::{Modifier, Note, NoteLiteral},
-    parsing::Parser,
+    chord::{
+        intervals::Interval,
+        note::{Modifier, Note, NoteLiteral},
+        quality::Quality,
+    },
+    parsing::{
+        expression::Exp, expressions::ExtensionExp, parser_config::ParserConfig,
+        parser_error::ParserError, Parser,
+    },
 };
 
 use test_case::test_case;
@@ -189,6 +196,10 @@ use test_case::test_case;
 #[test_case("Cdim7sus4", vec!["C", "F", "Gb", "B𝄫"])]
 #[test_case("Csusdim7maj7", vec!["C", "F", "Gb", "B𝄫", "B"])]
 #[test_case(" C+susMaj76", vec!["C", "F", "G#", "A", "B"])]
+#[test_case("Bbb", vec!["B𝄫", "Db", "Fb"])]
+#[test_case("B♭♭m7", vec!["B𝄫", "D𝄫", "Fb", "A𝄫"])]
+#[test_case("F##", vec!["F𝄪", "A𝄪", "C𝄪"])]
+#[test_case("Cbb7", vec!["C𝄫", "E𝄫", "G𝄫", "Ab"])]
 fn test_notes(i: &str, expected: Vec<&str>) {
     let mut parser = Parser::new();
     let res = parser.parse(i);
@@ -250,3 +261,651 @@ fn test_notes(i: &str, expected: Vec<&str>) {
         }
     }
 }
+
+#[test_case("  Cmaj7  ", "Cmaj7")]
+#[test_case("C  maj7", "C maj7")]
+#[test_case("\tCmi7\n", "Cmi7")]
+#[test_case("C13  b9   #9", "C13 b9 #9")]
+fn test_whitespace(input: &str, expected_origin: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let trimmed = parser
+        .parse(&input.split_whitespace().collect::<String>())
+        .unwrap();
+    assert_eq!(chord.origin, expected_origin);
+    assert_eq!(chord.normalized, trimmed.normalized);
+    assert_eq!(chord.real_intervals, trimmed.real_intervals);
+    assert_eq!(chord.note_literals, trimmed.note_literals);
+}
+
+#[test_case("Bbb", "", "B𝄫")]
+#[test_case("F##7", "7", "F𝄪7")]
+#[test_case("C##5", "#5", "C#(#5)")]
+#[test_case("Cbb9", "b9", "Cb(b9)")]
+#[test_case("Bbbm7/Fb", "m7/Fb", "B𝄫min7/Fb")]
+#[test_case("C7/C", "7/C", "C7/C")]
+fn test_double_accidental_roots(input: &str, descriptor: &str, normalized: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.descriptor, descriptor);
+    assert_eq!(chord.normalized, normalized);
+}
+
+#[test]
+fn test_parse_stream() {
+    let input = "Cmaj7 Dm7\n\nG7(b9)  Cmaj7/H\n  Am7 ";
+    let mut parser = Parser::new();
+    let res = parser
+        .parse_stream(std::io::Cursor::new(input))
+        .map(|r| r.map(|chord| chord.normalized))
+        .collect::<Vec<_>>();
+    assert_eq!(res.len(), 5);
+    assert_eq!(res[0].as_ref().unwrap(), "CMaj7");
+    assert_eq!(res[1].as_ref().unwrap(), "Dmin7");
+    assert_eq!(res[2].as_ref().unwrap(), "G7(b9)");
+    assert!(res[3].is_err());
+    assert_eq!(res[4].as_ref().unwrap(), "Amin7");
+}
+
+#[test]
+fn test_parse_all() {
+    let mut parser = Parser::new();
+    let res = parser.parse_all(&["Cmaj7", "C((9", "", "G7(b9)", "Cmaj7/H", "Am7"]);
+    assert_eq!(res.len(), 6);
+    assert_eq!(res[0].as_ref().unwrap().normalized, "CMaj7");
+    assert!(res[1].is_err());
+    assert_eq!(
+        res[2].as_ref().unwrap_err().errors,
+        vec![ParserError::MissingRootNote]
+    );
+    assert_eq!(res[3].as_ref().unwrap().normalized, "G7(b9)");
+    assert_eq!(
+        res[4].as_ref().unwrap_err().errors,
+        parser.parse("Cmaj7/H").unwrap_err().errors
+    );
+    assert_eq!(res[5].as_ref().unwrap().normalized, "Amin7");
+    assert!(parser.parse_all(&[]).is_empty());
+}
+
+#[test]
+fn test_parse_progression() {
+    let mut parser = Parser::new();
+    let res = parser.parse_progression("N.C. Cmaj7  %\tNC % Dm7 Cmaj7/H % G7");
+    let normalized = res
+        .iter()
+        .map(|r| {
+            r.as_ref()
+                .map(|chord| chord.as_ref().map(|c| c.normalized.as_str()))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(normalized.len(), 9);
+    assert_eq!(normalized[0], Ok(None));
+    assert_eq!(normalized[1], Ok(Some("CMaj7")));
+    assert_eq!(normalized[2], Ok(Some("CMaj7")));
+    assert_eq!(normalized[3], Ok(None));
+    assert_eq!(normalized[4], Ok(None));
+    assert_eq!(normalized[5], Ok(Some("Dmin7")));
+    assert!(normalized[6].is_err());
+    assert_eq!(normalized[7], Ok(Some("Dmin7")));
+    assert_eq!(normalized[8], Ok(Some("G7")));
+    assert!(parser.parse_progression(" ").is_empty());
+    assert!(parser.parse_progression("%")[0].is_err());
+    assert!(parser.parse("N.C.").is_err());
+    assert!(parser.parse("NC").is_err());
+}
+
+#[test]
+fn test_parse_all_repeat_sign() {
+    let mut parser = Parser::new();
+    let res = parser.parse_all(&["%", "Cmaj7", "%", " % ", "Cmaj7/H", "%", "Dm7", "%"]);
+    let normalized = res
+        .iter()
+        .map(|r| r.as_ref().map(|chord| chord.normalized.as_str()).ok())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        normalized,
+        vec![
+            None,
+            Some("CMaj7"),
+            Some("CMaj7"),
+            Some("CMaj7"),
+            None,
+            Some("CMaj7"),
+            Some("Dmin7"),
+            Some("Dmin7")
+        ]
+    );
+    assert_eq!(res[0], parser.parse("%"));
+    assert!(parser.parse("%").is_err());
+
+    let stream = parser
+        .parse_stream(std::io::Cursor::new("C %\n% G7 %"))
+        .map(|r| r.map(|chord| chord.normalized).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(stream, vec!["C", "C", "C", "G7", "G7"]);
+}
+
+#[test]
+fn test_parser_is_reusable_after_error() {
+    let mut parser = Parser::new();
+    assert!(parser.parse("Cmaj7/H").is_err());
+    assert!(parser.parse("C7b9add9").is_err());
+    assert_eq!(parser.parse("Cm7").unwrap().normalized, "Cmin7");
+}
+
+#[test_case("Cadd9", vec!["C", "E", "G", "D"], false)]
+#[test_case("C9", vec!["C", "E", "G", "Bb", "D"], true)]
+#[test_case("Cadd11", vec!["C", "E", "G", "F"], false)]
+#[test_case("C(add11)", vec!["C", "E", "G", "F"], false)]
+#[test_case("C11", vec!["C", "G", "Bb", "D", "F"], true)]
+#[test_case("Cadd#11", vec!["C", "E", "G", "F#"], false)]
+#[test_case("C7#11", vec!["C", "E", "G", "Bb", "F#"], true)]
+#[test_case("Cadd13", vec!["C", "E", "G", "A"], false)]
+#[test_case("C13", vec!["C", "E", "G", "Bb", "D", "A"], true)]
+#[test_case("Cmadd11", vec!["C", "Eb", "G", "F"], false)]
+fn test_add_never_implies_seventh(input: &str, expected: Vec<&str>, has_seventh: bool) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.note_literals, expected);
+    assert_eq!(
+        chord.real_intervals.contains(&Interval::MinorSeventh),
+        has_seventh
+    );
+}
+
+#[test_case("C(omit3 5)", vec!["C"]; "omit group with space")]
+#[test_case("C7(omit3 5)", vec!["C", "Bb"]; "seventh omit group with space")]
+#[test_case("C(add9 13)", vec!["C", "E", "G", "D", "A"]; "add group with space")]
+#[test_case("C(omit3, 5)", vec!["C"]; "omit group with comma and space")]
+#[test_case("C(b9 #9)", vec!["C", "E", "G", "Db", "D#"]; "alterations with space")]
+fn test_space_separated_group_items(input: &str, expected: Vec<&str>) {
+    let config = ParserConfig {
+        space_separated_group_items: true,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.note_literals, expected);
+}
+
+#[test]
+fn test_space_separated_group_items_default() {
+    let mut parser = Parser::new();
+    let chord = parser.parse("C(omit3 5)").unwrap();
+    assert_eq!(chord.note_literals, vec!["C", "G"]);
+}
+
+#[test_case("C5/G", "G")]
+#[test_case("C5/E", "E")]
+#[test_case("F#5/C#", "C#")]
+fn test_power_chord_with_bass(input: &str, bass: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.quality, Quality::Power);
+    assert_eq!(
+        chord.real_intervals,
+        vec![Interval::Unison, Interval::PerfectFifth]
+    );
+    assert_eq!(chord.bass.map(|b| b.to_string()), Some(bass.to_string()));
+}
+
+#[test_case("C9", vec!["C", "E", "G", "D"])]
+#[test_case("C13", vec!["C", "E", "G", "A"])]
+#[test_case("Cm11", vec!["C", "Eb", "G", "F"])]
+#[test_case("Cm13", vec!["C", "Eb", "G", "A"])]
+#[test_case("C7(13)", vec!["C", "E", "G", "Bb", "A"])]
+#[test_case("CMaj13", vec!["C", "E", "G", "B", "A"])]
+#[test_case("C9sus4", vec!["C", "F", "G", "D"])]
+fn test_literal_extensions(input: &str, expected: Vec<&str>) {
+    let config = ParserConfig {
+        imply_extensions: false,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.note_literals, expected);
+}
+
+#[test_case("C+Maj7", "CMaj7#5", "CMaj7(#5)")]
+#[test_case("CaugMaj7", "CMaj7#5", "CMaj7(#5)")]
+#[test_case("CMaj7+", "CMaj7(#5)", "CMaj7(#5)")]
+#[test_case("C+7", "C7#5", "C7(#5)")]
+#[test_case("C7+5", "C7#5", "C7(#5)")]
+#[test_case("Caug7", "C7(#5)", "C7(#5)")]
+#[test_case("C+", "C(#5)", "C(#5)")]
+#[test_case("C+9", "C9#5", "C9(#5)")]
+fn test_augmented_fifth_equivalence(a: &str, b: &str, normalized: &str) {
+    let mut parser = Parser::new();
+    let a = parser.parse(a).unwrap();
+    let b = parser.parse(b).unwrap();
+    assert_eq!(a.real_intervals, b.real_intervals);
+    assert_eq!(a.note_literals, b.note_literals);
+    assert_eq!(a.quality, b.quality);
+    assert_eq!(a.normalized, normalized);
+    assert_eq!(b.normalized, normalized);
+}
+
+#[test_case("Cm7b5")]
+#[test_case("Cmi7(b5)")]
+#[test_case("Cm7(b5)")]
+#[test_case("Cmin7b5")]
+#[test_case("C-7b5")]
+#[test_case("Cø7")]
+#[test_case("Cø")]
+fn test_half_diminished_equivalence(input: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let expected = parser.parse("Cm7b5").unwrap();
+    assert_eq!(chord.real_intervals, expected.real_intervals);
+    assert_eq!(chord.note_literals, vec!["C", "Eb", "Gb", "Bb"]);
+    assert_eq!(chord.quality, Quality::Minor);
+    assert_eq!(chord.normalized, "Cmin7(b5)");
+}
+
+#[test_case("Cma9omit3"; "ma ninth omit third")]
+#[test_case("Cma9(omit3,5)"; "ma ninth omit group")]
+#[test_case("C7omit5"; "seventh omit fifth")]
+#[test_case("C(omit3)"; "omit third group")]
+#[test_case("Comit5"; "omit fifth")]
+#[test_case("C7OMIT3"; "upper case omit")]
+#[test_case("C7Omit3"; "capitalized omit")]
+#[test_case("Cadd9(omit3)"; "add ninth omit third")]
+#[test_case("Cm7(omit5,add11)"; "minor omit and add group")]
+fn test_no_is_omit(input: &str) {
+    let mut parser = Parser::new();
+    let with_omit = parser.parse(input).unwrap();
+    let with_no = parser
+        .parse(
+            &input
+                .replace("omit", "no")
+                .replace("OMIT", "NO")
+                .replace("Omit", "No"),
+        )
+        .unwrap();
+    assert_eq!(with_omit.real_intervals, with_no.real_intervals);
+    assert_eq!(with_omit.normalized, with_no.normalized);
+}
+
+#[test_case("Cm(maj7)", "CmMaj7")]
+#[test_case("C-(Maj7)", "C-Maj7")]
+#[test_case("Cm(Maj9)", "CmMaj9")]
+#[test_case("C(m7)", "Cm7")]
+#[test_case("C(maj7)", "Cmaj7")]
+#[test_case("Cm(7)", "Cm7")]
+#[test_case("C(m)", "Cm")]
+#[test_case("C(dim)", "Cdim")]
+#[test_case("C(aug)", "Caug")]
+#[test_case("C7(sus4)", "C7sus4")]
+#[test_case("C7(alt)", "C7alt")]
+#[test_case("C(add9)(b5)", "C(b5,add9)")]
+#[test_case("Cm7(b5)(add11)", "Cm7b5add11")]
+fn test_parenthesized_quality(input: &str, equivalent: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    let expected = parser.parse(equivalent).unwrap();
+    assert_eq!(chord.real_intervals, expected.real_intervals);
+    assert_eq!(chord.normalized, expected.normalized);
+    assert_eq!(chord.quality, expected.quality);
+}
+
+#[test]
+fn test_last_expressions() {
+    let mut parser = Parser::new();
+    parser.parse("Cm7(b5)/Gb").unwrap();
+    let names = parser
+        .last_expressions()
+        .iter()
+        .map(|e| e.stringify())
+        .collect::<Vec<String>>();
+    assert_eq!(names, vec!["Minor", "Extension", "Extension", "SlashBass"]);
+    assert!(matches!(
+        parser.last_expressions()[2],
+        Exp::Extension(ExtensionExp {
+            interval: Interval::MinorSeventh,
+            ..
+        })
+    ));
+
+    assert!(parser.parse("Cm7(b5").is_err());
+    assert_eq!(parser.last_expressions().len(), 3);
+
+    parser.parse("Cmaj7").unwrap();
+    assert!(parser.parse("  ").is_err());
+    assert!(parser.last_expressions().is_empty());
+}
+
+#[test_case("C11", vec!["C", "E", "G", "Bb", "D", "F"])]
+#[test_case("CMaj11", vec!["C", "E", "G", "B", "D", "F"])]
+#[test_case("Cm11", vec!["C", "Eb", "G", "Bb", "D", "F"])]
+#[test_case("C7sus11", vec!["C", "G", "Bb", "D", "F"])]
+#[test_case("C13", vec!["C", "E", "G", "Bb", "D", "A"])]
+fn test_eleventh_keeps_third(input: &str, expected: Vec<&str>) {
+    let config = ParserConfig {
+        eleventh_keeps_third: true,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.note_literals, expected);
+}
+
+#[test_case("Csus4(add9)", vec!["C", "F", "G", "D"])]
+#[test_case("C7sus", vec!["C", "F", "G", "Bb"])]
+#[test_case("Csus2", vec!["C", "G", "D"])]
+#[test_case("Cadd3", vec!["C", "E", "G"])]
+#[test_case("Cm(add11)", vec!["C", "Eb", "G", "F"])]
+fn test_reject_sus_with_third_accepts_plain_sus(input: &str, expected: Vec<&str>) {
+    let config = ParserConfig {
+        reject_sus_with_third: true,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.note_literals, expected);
+}
+
+#[test_case("Do", "C")]
+#[test_case("Domaj7", "CMaj7")]
+#[test_case("Rem7", "Dmin7")]
+#[test_case("Mimi7", "Emin7")]
+#[test_case("Mim7b5", "Emin7(b5)")]
+#[test_case("Fa#dim", "F#dim")]
+#[test_case("Sol7sus4", "G7sus")]
+#[test_case("Solb", "Gb")]
+#[test_case("La-", "Amin")]
+#[test_case("Sib7/Re", "Bb7/D")]
+#[test_case("Ti", "B")]
+#[test_case("Doo7", "Cdim7")]
+#[test_case("Do(add9)/Mi", "C(add9)/E")]
+#[test_case("Re/Fa#", "D/F#")]
+fn test_solfege_notes(input: &str, expected: &str) {
+    let config = ParserConfig {
+        solfege_notes: true,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.normalized, expected);
+    assert_eq!(chord.origin, input);
+    let english = Parser::new().parse(expected).unwrap();
+    assert_eq!(chord.notes, english.notes);
+}
+
+#[test_case("C minor seven", "Cm7")]
+#[test_case("F major seventh flat five", "FMaj7b5")]
+#[test_case("C sharp minor", "C#m")]
+#[test_case("B flat seven sharp nine", "Bb7#9")]
+#[test_case("G seven suspended four", "G7sus4")]
+#[test_case("D half diminished seven", "Dø7")]
+#[test_case("E diminished seventh", "Edim7")]
+#[test_case("A augmented", "A+")]
+#[test_case("C six nine", "C69")]
+#[test_case("C add nine", "Cadd9")]
+#[test_case("C seven no three", "C7no3")]
+#[test_case("G over B", "G/B")]
+#[test_case("C Minor Eleventh", "Cm11")]
+#[test_case("C minor 7", "Cm7")]
+#[test_case("Cmaj7 sharp eleven", "Cmaj7#11")]
+fn test_spelled_out_words(input: &str, expected: &str) {
+    let config = ParserConfig {
+        spelled_out_words: true,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    let chord = parser.parse(input).unwrap();
+    let symbolic = Parser::new().parse(expected).unwrap();
+    assert_eq!(chord.normalized, symbolic.normalized);
+    assert_eq!(chord.notes, symbolic.notes);
+    assert_eq!(chord.origin, input);
+}
+
+#[test_case("ii7", "Rem7")]
+#[test_case("bVII", "Sib")]
+#[test_case("viio7", "Sio7")]
+fn test_solfege_degrees(input: &str, expected: &str) {
+    let config = ParserConfig {
+        solfege_notes: true,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    let key = Note::new(NoteLiteral::C, None);
+    let chord = parser.parse_degree(input, &key);
+    assert_eq!(chord.map(|c| c.origin), Ok(expected.to_string()));
+}
+
+#[test_case("I", Note::new(NoteLiteral::C, None), "C")]
+#[test_case("ii7", Note::new(NoteLiteral::C, None), "Dmin7")]
+#[test_case("iii", Note::new(NoteLiteral::C, None), "Emin")]
+#[test_case("IVmaj7", Note::new(NoteLiteral::C, None), "FMaj7")]
+#[test_case("V7", Note::new(NoteLiteral::C, None), "G7")]
+#[test_case("vi9", Note::new(NoteLiteral::C, None), "Amin9")]
+#[test_case("viio7", Note::new(NoteLiteral::C, None), "Bdim7")]
+#[test_case("viiø7", Note::new(NoteLiteral::C, None), "Bmin7(b5)")]
+#[test_case("bVImaj7", Note::new(NoteLiteral::C, None), "AbMaj7")]
+#[test_case("bVII7", Note::new(NoteLiteral::C, None), "Bb7")]
+#[test_case("#iv7b5", Note::new(NoteLiteral::C, None), "F#min7(b5)")]
+#[test_case("iisus4", Note::new(NoteLiteral::C, None), "Dsus")]
+#[test_case("V7", Note::new(NoteLiteral::E, Some(Modifier::Flat)), "Bb7")]
+#[test_case("IV", Note::new(NoteLiteral::G, Some(Modifier::Flat)), "Cb")]
+#[test_case("iimaj7", Note::new(NoteLiteral::D, None), "EminMaj7")]
+fn test_parse_degree(input: &str, key: Note, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse_degree(input, &key);
+    assert_eq!(chord.map(|c| c.normalized).unwrap_or_default(), expected);
+}
+
+#[test_case("Cdimbb7", "Cdim7", vec!["C", "Eb", "Gb", "B𝄫"])]
+#[test_case("Cmb5bb7", "Cdim7", vec!["C", "Eb", "Gb", "B𝄫"])]
+#[test_case("Cm(b5,bb7)", "Cdim7", vec!["C", "Eb", "Gb", "B𝄫"])]
+#[test_case("Cmbb7", "Cmin6", vec!["C", "Eb", "G", "B𝄫"])]
+#[test_case("Cbb7", "C𝄫7", vec!["C𝄫", "E𝄫", "G𝄫", "Ab"])]
+fn test_double_flat_seventh(input: &str, expected: &str, notes: Vec<&str>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.normalized, expected);
+    assert_eq!(chord.note_literals, notes);
+}
+
+#[test_case(Note::new(NoteLiteral::C, None), "", "C", "C")]
+#[test_case(Note::new(NoteLiteral::C, None), "maj7#11", "CMaj7(#11)", "Cmaj7#11")]
+#[test_case(Note::new(NoteLiteral::C, None), "#5", "C(#5)", "C#5")]
+#[test_case(Note::new(NoteLiteral::C, None), "b9", "C(b9)", "Cb9")]
+#[test_case(
+    Note::new(NoteLiteral::F, Some(Modifier::Sharp)),
+    "m7b5",
+    "F#min7(b5)",
+    "F#m7b5"
+)]
+#[test_case(
+    Note::new(NoteLiteral::B, Some(Modifier::Flat)),
+    "7/D",
+    "Bb7/D",
+    "Bb7/D"
+)]
+#[test_case(Note::new(NoteLiteral::E, None), "min 9", "Emin9", "Emin 9")]
+fn test_parse_descriptor(root: Note, descriptor: &str, expected: &str, origin: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse_descriptor(root.clone(), descriptor).unwrap();
+    assert_eq!(chord.normalized, expected);
+    assert_eq!(chord.origin, origin);
+    assert_eq!(chord.root, root);
+}
+
+#[test_case("C7(passing)", "C7"; "passing")]
+#[test_case("Am(hold)", "Amin"; "hold")]
+#[test_case("Am( HOLD )", "Amin"; "padded upper case hold")]
+#[test_case("G7(b9)(pass)", "G7(b9)"; "pass after group")]
+#[test_case("Dm7(pass)/C", "Dmin7/C"; "pass before bass")]
+#[test_case("C7(b9)", "C7(b9)"; "no annotation")]
+fn test_ignored_annotations(input: &str, expected: &str) {
+    let config = ParserConfig {
+        ignored_annotations: vec![
+            "passing".to_string(),
+            "pass".to_string(),
+            "hold".to_string(),
+        ],
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.normalized, expected);
+}
+
+#[test]
+fn test_annotations_are_errors_unless_ignored() {
+    let mut parser = Parser::new();
+    assert!(parser.parse("C7(passing)").is_err());
+
+    let config = ParserConfig {
+        ignored_annotations: vec!["passing".to_string()],
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    assert!(parser.parse("C7(b9,passing)").is_err());
+    assert!(parser.parse("C7(fermata)").is_err());
+    assert_eq!(
+        parser.parse("(passing)").unwrap_err().errors,
+        vec![ParserError::MissingRootNote]
+    );
+    let err = parser.parse("C7(passing)b4").unwrap_err();
+    assert_eq!(
+        err.errors,
+        parser
+            .parse("C7b4")
+            .unwrap_err()
+            .errors
+            .iter()
+            .map(|e| match e {
+                ParserError::InvalidExtension(pos) => ParserError::InvalidExtension(pos + 9),
+                e => e.clone(),
+            })
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test_case("Cm/maj7", "CminMaj7", None; "lower case maj")]
+#[test_case("Cm/Maj7", "CminMaj7", None; "capitalized maj")]
+#[test_case("Cm/M7", "CminMaj7", None; "M")]
+#[test_case("C-/△", "CminMaj7", None; "triangle")]
+#[test_case("Cm/maj9", "CminMaj9", None; "major ninth")]
+#[test_case("Cm/maj7(#11)", "CminMaj7(#11)", None; "with alteration")]
+#[test_case("Cm/maj7/G", "CminMaj7/G", Some("G"); "with bass")]
+fn test_slash_major_seventh(input: &str, expected: &str, bass: Option<&str>) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.normalized, expected);
+    assert_eq!(chord.bass.map(|b| b.to_string()).as_deref(), bass);
+}
+
+#[test_case("C△", "CMaj7", "C"; "triangle triad")]
+#[test_case("CΔ", "CMaj7", "C"; "Cdelta triad")]
+#[test_case("C^", "CMaj7", "C"; "caret triad")]
+#[test_case("C△7", "CMaj7", "CMaj7"; "triangle seventh")]
+#[test_case("C△9", "CMaj9", "CMaj9"; "triangle ninth")]
+#[test_case("C△(#11)", "CMaj7(#11)", "C(#11)"; "triangle sharp eleventh")]
+#[test_case("C-△", "CminMaj7", "Cmin"; "minor triangle")]
+#[test_case("C-△7", "CminMaj7", "CminMaj7"; "minor triangle seventh")]
+#[test_case("C△/E", "CMaj7/E", "C/E"; "triangle over bass")]
+fn test_triangle_implies_seventh(input: &str, default: &str, expected: &str) {
+    let config = ParserConfig {
+        triangle_implies_seventh: false,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    assert_eq!(parser.parse(input).unwrap().normalized, expected);
+    assert_eq!(Parser::new().parse(input).unwrap().normalized, default);
+}
+
+#[test_case("C7,9,13", Ok("C13"), "C13"; "extensions")]
+#[test_case("C7,b9,#11", Ok("C7(b9,#11)"), "C7(b9,#11)"; "alterations")]
+#[test_case("C,add9,11", Ok("C9sus"), "C(add9,11)"; "add list after root")]
+#[test_case("Cadd9,11", Ok("C9sus"), "C(add9,11)"; "add list")]
+#[test_case("C7,omit3,5", Err(()), "C7(omit3,5)"; "omit list")]
+#[test_case("Cm7,add11", Ok("Cmin7(add11)"), "Cmin7(add11)"; "add after quality")]
+#[test_case("C7(b9),#11", Ok("C7(b9,#11)"), "C7(b9,#11)"; "alteration after group")]
+#[test_case("C(omit3,5),add9", Ok("C(add9,omit3,5)"), "C(add9,omit3,5)"; "add after omit group")]
+fn test_comma_separated_extensions(input: &str, default: Result<&str, ()>, expected: &str) {
+    let config = ParserConfig {
+        comma_separated_extensions: true,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    assert_eq!(parser.parse(input).unwrap().normalized, expected);
+    let res = Parser::new().parse(input);
+    assert_eq!(
+        res.as_ref().map(|c| c.normalized.as_str()).map_err(|_| ()),
+        default
+    );
+}
+
+#[test_case("CM", "C", "CMaj7")]
+#[test_case("CMaj", "C", "CMaj7")]
+#[test_case("Cmajor", "C", "CMaj7")]
+#[test_case("CM7", "CMaj7", "CMaj7")]
+#[test_case("CMaj9", "CMaj9", "CMaj9")]
+#[test_case("CM13", "CMaj13", "CMaj13")]
+#[test_case("CM6", "C6", "C6")]
+#[test_case("CM/E", "C/E", "CMaj7/E")]
+#[test_case("CMaj(#11)", "C(#11)", "CMaj7(#11)")]
+#[test_case("CMadd9", "C(add9)", "CMaj9")]
+#[test_case("CmMaj", "Cmin", "CminMaj7")]
+#[test_case("CmMaj7", "CminMaj7", "CminMaj7")]
+#[test_case("C△", "CMaj7", "CMaj7")]
+fn test_bare_major_implies_seventh(input: &str, default: &str, expected: &str) {
+    let config = ParserConfig {
+        bare_major_implies_seventh: true,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_config(config);
+    assert_eq!(parser.parse(input).unwrap().normalized, expected);
+    assert_eq!(Parser::new().parse(input).unwrap().normalized, default);
+}
+
+#[test_case("CM"; "M")]
+#[test_case("CMa"; "capitalized ma")]
+#[test_case("Cma"; "lower case ma")]
+#[test_case("CMA"; "upper case ma")]
+#[test_case("Cmaj"; "lower case maj")]
+#[test_case("CMaj"; "capitalized maj")]
+#[test_case("CMAJ"; "upper case maj")]
+#[test_case("Cmajor"; "lower case major")]
+#[test_case("CMajor"; "capitalized major")]
+#[test_case("CMAJOR"; "upper case major")]
+fn test_major_keywords(keyword: &str) {
+    let mut parser = Parser::new();
+    let triad = parser.parse(keyword).unwrap();
+    assert_eq!(triad.normalized, "C");
+    assert_eq!(triad.note_literals, vec!["C", "E", "G"]);
+
+    for (ext, expected) in [
+        ("7", "CMaj7"),
+        ("9", "CMaj9"),
+        ("13", "CMaj13"),
+        ("7#11", "CMaj7(#11)"),
+        ("6", "C6"),
+    ] {
+        let chord = parser.parse(&format!("{}{}", keyword, ext)).unwrap();
+        assert_eq!(chord.normalized, expected, "{}{}", keyword, ext);
+    }
+    let minor_major = parser.parse(&format!("Cm{}7", &keyword[1..])).unwrap();
+    assert_eq!(minor_major.normalized, "CminMaj7");
+}
+
+#[test_case("C7sus", vec!["C", "F", "G", "Bb"], "C7sus")]
+#[test_case("C7sus4", vec!["C", "F", "G", "Bb"], "C7sus")]
+#[test_case("C7sus2", vec!["C", "G", "Bb", "D"], "C9(omit3)")]
+#[test_case("C9sus", vec!["C", "F", "G", "Bb", "D"], "C9sus")]
+#[test_case("C9sus4", vec!["C", "F", "G", "Bb", "D"], "C9sus")]
+#[test_case("C9sus2", vec!["C", "G", "Bb", "D"], "C9(omit3)")]
+#[test_case("C13sus", vec!["C", "F", "G", "Bb", "D", "A"], "C13sus")]
+#[test_case("C13sus4", vec!["C", "F", "G", "Bb", "D", "A"], "C13sus")]
+#[test_case("C13sus2", vec!["C", "G", "Bb", "D", "A"], "C13(omit3)")]
+#[test_case("C7(sus4)", vec!["C", "F", "G", "Bb"], "C7sus")]
+fn test_seventh_sus_family(input: &str, notes: Vec<&str>, expected: &str) {
+    let mut parser = Parser::new();
+    let chord = parser.parse(input).unwrap();
+    assert_eq!(chord.note_literals, notes);
+    assert_eq!(chord.normalized, expected);
+    assert_eq!(chord.quality, Quality::Dominant);
+    assert!(!chord.has_doublings());
+}